@@ -15,8 +15,12 @@ pub struct CodeElement {
     code_type: CodeType,
     language: Option<String>,
     file_path: Option<String>,
+    #[builder(skip)]
+    comment: Option<String>,
 }
 
+super::macros::impl_str_setters!(CodeElementBuilder, code_element_builder);
+
 impl<S: code_element_builder::IsComplete> CodeElementBuilder<S> {
     pub fn build(self) -> Result<CodeElement, CodeElementError> {
         let code_element = self.build_internal();
@@ -44,6 +48,14 @@ impl CodeElement {
     pub fn file_path(&self) -> Option<&str> {
         self.file_path.as_deref()
     }
+    /// Compares two code elements, ignoring their identifiers.
+    pub fn structural_eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.description == other.description
+            && self.code_type == other.code_type
+            && self.language == other.language
+            && self.file_path == other.file_path
+    }
 }
 
 super::macros::impl_element!(CodeElement, ElementType::Code);
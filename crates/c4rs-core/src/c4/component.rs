@@ -1,6 +1,6 @@
 use super::code::CodeElement;
-use super::element::{ElementId, ElementType};
-use super::macros::impl_element;
+use super::element::{ComponentVisibility, ElementId, ElementType};
+use super::macros::{impl_element, impl_str_setters};
 use crate::constants::limits::{
     MAX_DESCRIPTION_LENGTH, MAX_NAME_LENGTH, MAX_RESPONSIBILITY_LENGTH, MAX_TECHNOLOGY_LENGTH,
 };
@@ -12,22 +12,55 @@ use bon::Builder;
 pub struct Component {
     #[builder(field)]
     code_elements: Vec<CodeElement>,
+    #[builder(field)]
+    technologies: Vec<String>,
     name: String,
     description: String,
     #[builder(skip = ElementId::from_name(&name))]
     id: ElementId,
     #[builder(default)]
     responsibilities: Vec<String>,
+    /// Emitted as a `"Public API"` or `"Internal"` tag when set.
+    visibility: Option<ComponentVisibility>,
+    #[builder(default)]
+    tags: Vec<String>,
+    /// `technologies` joined with `", "`, computed once at build time.
+    #[builder(skip)]
     technology: Option<String>,
+    #[builder(skip)]
+    comment: Option<String>,
 }
 
+impl<S: component_builder::State> ComponentBuilder<S> {
+    /// Sets a single technology, replacing any previously added ones.
+    pub fn technology(mut self, technology: String) -> Self {
+        self.technologies = vec![technology];
+        self
+    }
+    /// Appends a technology; multiple technologies are joined with `", "`.
+    pub fn add_technology(mut self, technology: String) -> Self {
+        self.technologies.push(technology);
+        self
+    }
+}
+
+impl_str_setters!(ComponentBuilder, component_builder);
+
 impl<S: component_builder::IsComplete> ComponentBuilder<S> {
     pub fn add_code_element(mut self, code_element: &CodeElement) -> Self {
         self.code_elements.push(code_element.clone());
         self
     }
     pub fn build(self) -> Result<Component, ComponentError> {
-        let component = self.build_internal();
+        self.try_build()
+    }
+    /// Builds and validates the component. Over-long technologies are reported
+    /// as [`ComponentError::TechnologyTooLong`] rather than a generic validation
+    /// error.
+    pub fn try_build(self) -> Result<Component, ComponentError> {
+        let mut component = self.build_internal();
+        component.technology =
+            (!component.technologies.is_empty()).then(|| component.technologies.join(", "));
         validate_non_empty(&component.name, "name")?;
         validate_max_length(&component.name, MAX_NAME_LENGTH, "name")?;
         validate_non_empty(&component.description, "description")?;
@@ -36,7 +69,14 @@ impl<S: component_builder::IsComplete> ComponentBuilder<S> {
             MAX_DESCRIPTION_LENGTH,
             "description",
         )?;
-        validate_max_length(&component.technology, MAX_TECHNOLOGY_LENGTH, "technology")?;
+        if let Some(technology) = component.technology()
+            && technology.len() > MAX_TECHNOLOGY_LENGTH
+        {
+            return Err(ComponentError::TechnologyTooLong {
+                max: MAX_TECHNOLOGY_LENGTH,
+                actual: technology.len(),
+            });
+        }
         validate_vec_max_length(
             &component.responsibilities,
             MAX_RESPONSIBILITY_LENGTH,
@@ -50,18 +90,48 @@ impl Component {
     pub fn responsibilities(&self) -> &[String] {
         &self.responsibilities
     }
+    /// Returns the technologies joined with `", "`, or `None` if there are none.
     pub fn technology(&self) -> Option<&str> {
         self.technology.as_deref()
     }
+    pub fn technologies(&self) -> &[String] {
+        &self.technologies
+    }
+    pub fn visibility(&self) -> Option<ComponentVisibility> {
+        self.visibility
+    }
     pub fn code_elements(&self) -> &[CodeElement] {
         &self.code_elements
     }
     pub fn add_code_element(&mut self, code_element: &CodeElement) {
         self.code_elements.push(code_element.clone());
     }
+    /// Removes and returns the first code element with the given name.
+    pub fn remove_code_element(&mut self, name: &str) -> Option<CodeElement> {
+        let index = self.code_elements.iter().position(|c| c.name() == name)?;
+        Some(self.code_elements.remove(index))
+    }
+    pub fn code_element_mut(&mut self, name: &str) -> Option<&mut CodeElement> {
+        self.code_elements.iter_mut().find(|c| c.name() == name)
+    }
+    /// Compares two components and their code elements, ignoring identifiers.
+    pub fn structural_eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.description == other.description
+            && self.responsibilities == other.responsibilities
+            && self.technologies == other.technologies
+            && self.visibility == other.visibility
+            && self.tags == other.tags
+            && self.code_elements.len() == other.code_elements.len()
+            && self
+                .code_elements
+                .iter()
+                .zip(&other.code_elements)
+                .all(|(a, b)| a.structural_eq(b))
+    }
 }
 
-impl_element!(Component, ElementType::Component);
+impl_element!(Component, ElementType::Component, fixed, tags);
 
 #[derive(Debug, thiserror::Error)]
 pub enum ComponentError {
@@ -97,4 +167,17 @@ mod tests {
         assert_eq!(c.name(), "Handler");
         assert_eq!(c.id().as_str(), "h");
     }
+    #[test]
+    fn test_try_build_technology_too_long() {
+        let result = Component::builder()
+            .name("Service".into())
+            .description("D".into())
+            .technology("x".repeat(MAX_TECHNOLOGY_LENGTH + 1))
+            .try_build();
+        assert!(matches!(
+            result,
+            Err(ComponentError::TechnologyTooLong { max, actual })
+                if max == MAX_TECHNOLOGY_LENGTH && actual == MAX_TECHNOLOGY_LENGTH + 1
+        ));
+    }
 }
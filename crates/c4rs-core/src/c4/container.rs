@@ -1,6 +1,6 @@
 use super::component::Component;
 use super::element::{ContainerType, ElementId, ElementType};
-use super::macros::impl_element;
+use super::macros::{impl_element, impl_str_setters};
 use crate::constants::limits::{MAX_DESCRIPTION_LENGTH, MAX_NAME_LENGTH, MAX_TECHNOLOGY_LENGTH};
 use crate::validation::{validate_max_length, validate_non_empty};
 use bon::Builder;
@@ -10,12 +10,53 @@ use bon::Builder;
 pub struct Container {
     #[builder(field)]
     components: Vec<Component>,
+    #[builder(field)]
+    technologies: Vec<String>,
+    /// Set by `without_description()`; lets `build()` accept an empty
+    /// description.
+    #[builder(field)]
+    description_optional: bool,
     name: String,
     description: String,
     #[builder(skip = ElementId::from_name(&name))]
     id: ElementId,
     container_type: ContainerType,
+    #[builder(default)]
+    tags: Vec<String>,
+    /// Number of running instances, e.g. for deployment summaries.
+    instances: Option<u32>,
+    /// `technologies` joined with `", "`, computed once at build time.
+    #[builder(skip)]
     technology: Option<String>,
+    #[builder(skip)]
+    comment: Option<String>,
+}
+
+impl<S: container_builder::State> ContainerBuilder<S> {
+    /// Sets a single technology, replacing any previously added ones.
+    pub fn technology(mut self, technology: String) -> Self {
+        self.technologies = vec![technology];
+        self
+    }
+    /// Appends a technology; multiple technologies are joined with `", "`.
+    pub fn add_technology(mut self, technology: String) -> Self {
+        self.technologies.push(technology);
+        self
+    }
+}
+
+impl_str_setters!(ContainerBuilder, container_builder);
+
+impl<S: container_builder::State> ContainerBuilder<S> {
+    /// Builds the container without a description, which is then omitted
+    /// from generated output.
+    pub fn without_description(mut self) -> ContainerBuilder<container_builder::SetDescription<S>>
+    where
+        S::Description: container_builder::IsUnset,
+    {
+        self.description_optional = true;
+        self.description(String::new())
+    }
 }
 
 impl<S: container_builder::IsComplete> ContainerBuilder<S> {
@@ -24,16 +65,33 @@ impl<S: container_builder::IsComplete> ContainerBuilder<S> {
         self
     }
     pub fn build(self) -> Result<Container, ContainerError> {
-        let container = self.build_internal();
+        self.try_build()
+    }
+    /// Builds and validates the container. Over-long technologies are reported
+    /// as [`ContainerError::TechnologyTooLong`] rather than a generic validation
+    /// error.
+    pub fn try_build(self) -> Result<Container, ContainerError> {
+        let mut container = self.build_internal();
+        container.technology =
+            (!container.technologies.is_empty()).then(|| container.technologies.join(", "));
         validate_non_empty(&container.name, "name")?;
         validate_max_length(&container.name, MAX_NAME_LENGTH, "name")?;
-        validate_non_empty(&container.description, "description")?;
+        if !container.description_optional {
+            validate_non_empty(&container.description, "description")?;
+        }
         validate_max_length(
             &container.description,
             MAX_DESCRIPTION_LENGTH,
             "description",
         )?;
-        validate_max_length(&container.technology, MAX_TECHNOLOGY_LENGTH, "technology")?;
+        if let Some(technology) = container.technology()
+            && technology.len() > MAX_TECHNOLOGY_LENGTH
+        {
+            return Err(ContainerError::TechnologyTooLong {
+                max: MAX_TECHNOLOGY_LENGTH,
+                actual: technology.len(),
+            });
+        }
         Ok(container)
     }
 }
@@ -42,18 +100,64 @@ impl Container {
     pub fn container_type(&self) -> ContainerType {
         self.container_type.clone()
     }
+    /// Returns the technologies joined with `", "`, or `None` if there are none.
     pub fn technology(&self) -> Option<&str> {
         self.technology.as_deref()
     }
+    pub fn technologies(&self) -> &[String] {
+        &self.technologies
+    }
+    pub fn instances(&self) -> Option<u32> {
+        self.instances
+    }
     pub fn components(&self) -> &[Component] {
         &self.components
     }
     pub fn add_component(&mut self, component: &Component) {
         self.components.push(component.clone());
     }
+    /// Removes and returns the first component with the given name.
+    pub fn remove_component(&mut self, name: &str) -> Option<Component> {
+        let index = self.components.iter().position(|c| c.name() == name)?;
+        Some(self.components.remove(index))
+    }
+    /// Returns the first component with the given name.
+    pub fn component(&self, name: &str) -> Option<&Component> {
+        self.components.iter().find(|c| c.name() == name)
+    }
+    /// Returns the first component with the given name, mutably.
+    pub fn component_mut(&mut self, name: &str) -> Option<&mut Component> {
+        self.components.iter_mut().find(|c| c.name() == name)
+    }
+    /// Number of components plus the code elements inside them.
+    pub fn descendant_count(&self) -> usize {
+        self.components
+            .iter()
+            .map(|c| 1 + c.code_elements().len())
+            .sum()
+    }
+    /// Returns whether the container has no components.
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+    /// Compares two containers and their components, ignoring identifiers.
+    pub fn structural_eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.description == other.description
+            && self.container_type == other.container_type
+            && self.technologies == other.technologies
+            && self.tags == other.tags
+            && self.instances == other.instances
+            && self.components.len() == other.components.len()
+            && self
+                .components
+                .iter()
+                .zip(&other.components)
+                .all(|(a, b)| a.structural_eq(b))
+    }
 }
 
-impl_element!(Container, ElementType::Container);
+impl_element!(Container, ElementType::Container, fixed, tags);
 
 #[derive(Debug, thiserror::Error)]
 pub enum ContainerError {
@@ -84,4 +188,100 @@ mod tests {
         assert_eq!(c.name(), "API");
         assert_eq!(c.id().as_str(), "a");
     }
+    #[test]
+    fn test_container_structural_eq() {
+        let build = || {
+            let handler = Component::builder()
+                .name("Handler".into())
+                .description("Handles requests".into())
+                .build()
+                .unwrap();
+            Container::builder()
+                .name("API".into())
+                .description("REST".into())
+                .container_type(ContainerType::Api)
+                .add_component(&handler)
+                .build()
+                .unwrap()
+        };
+        let (a, mut b) = (build(), build());
+        assert!(a.structural_eq(&b));
+        b.remove_component("Handler");
+        assert!(!a.structural_eq(&b));
+    }
+    #[test]
+    fn test_remove_component() {
+        let handler = Component::builder()
+            .name("Handler".into())
+            .description("Handles requests".into())
+            .build()
+            .unwrap();
+        let mut c = Container::builder()
+            .name("API".into())
+            .description("REST".into())
+            .container_type(ContainerType::Api)
+            .add_component(&handler)
+            .build()
+            .unwrap();
+        assert!(c.component_mut("Handler").is_some());
+        assert_eq!(c.remove_component("Handler").unwrap().name(), "Handler");
+        assert!(c.components().is_empty());
+        assert!(c.remove_component("Handler").is_none());
+    }
+    #[test]
+    fn test_container_technologies() {
+        let c = Container::builder()
+            .name("API".into())
+            .description("REST".into())
+            .container_type(ContainerType::Api)
+            .add_technology("Rust".into())
+            .add_technology("Axum".into())
+            .build()
+            .unwrap();
+        assert_eq!(c.technology(), Some("Rust, Axum"));
+        let c = Container::builder()
+            .name("API".into())
+            .description("REST".into())
+            .container_type(ContainerType::Api)
+            .add_technology("Rust".into())
+            .technology("Go".into())
+            .build()
+            .unwrap();
+        assert_eq!(c.technologies(), ["Go"]);
+    }
+    #[test]
+    fn test_try_build_technology_too_long() {
+        let result = Container::builder()
+            .name("Service".into())
+            .description("D".into())
+            .container_type(ContainerType::Api)
+            .technology("x".repeat(MAX_TECHNOLOGY_LENGTH + 1))
+            .try_build();
+        assert!(matches!(
+            result,
+            Err(ContainerError::TechnologyTooLong { max, actual })
+                if max == MAX_TECHNOLOGY_LENGTH && actual == MAX_TECHNOLOGY_LENGTH + 1
+        ));
+    }
+    #[test]
+    fn test_element_tags_via_trait_object() {
+        use crate::c4::Element;
+        let c = Container::builder()
+            .name("Queue".into())
+            .description("Jobs".into())
+            .container_type(ContainerType::MessageBus)
+            .tags(vec!["Async".into(), "Critical".into()])
+            .build()
+            .unwrap();
+        let element: &dyn Element = &c;
+        assert_eq!(element.tags(), ["Async", "Critical"]);
+
+        let handler = Component::builder()
+            .name("Handler".into())
+            .description("Handles requests".into())
+            .build()
+            .unwrap();
+        let element: &dyn Element = &handler;
+        assert!(element.tags().is_empty());
+    }
 }
@@ -1,6 +1,7 @@
 use super::container::Container;
-use super::element::{ElementId, ElementType, Location};
-use super::macros::impl_element;
+use super::element::{Element, ElementId, ElementType, Location};
+use super::macros::{impl_element, impl_str_setters};
+use super::model::ModelRelationship;
 use crate::constants::limits::{MAX_DESCRIPTION_LENGTH, MAX_NAME_LENGTH, MAX_TECHNOLOGY_LENGTH};
 use crate::validation::{validate_max_length, validate_non_empty};
 use bon::Builder;
@@ -14,8 +15,20 @@ pub struct Person {
     id: ElementId,
     location: Option<Location>,
     technology: Option<String>,
+    /// User-defined tags, in addition to the implicit `External` tag for
+    /// external persons.
+    #[builder(default)]
+    tags: Vec<String>,
+    #[builder(skip)]
+    comment: Option<String>,
+    /// Relationships recorded with [`uses`](Person::uses), drained by
+    /// serializers when the person is added.
+    #[builder(skip)]
+    outgoing: Vec<ModelRelationship>,
 }
 
+impl_str_setters!(PersonBuilder, person_builder);
+
 impl<S: person_builder::IsComplete> PersonBuilder<S> {
     pub fn build(self) -> Result<Person, PersonError> {
         let person = self.build_internal();
@@ -28,7 +41,41 @@ impl<S: person_builder::IsComplete> PersonBuilder<S> {
     }
 }
 
-impl_element!(Person, ElementType::Person, optional);
+impl_element!(Person, ElementType::Person, optional, tags);
+
+impl Person {
+    pub fn technology(&self) -> Option<&str> {
+        self.technology.as_deref()
+    }
+    /// Records a relationship from this person to `target` and returns it.
+    /// Serializers pick up recorded relationships when the person is added.
+    pub fn uses(
+        &mut self,
+        target: &impl Element,
+        description: &str,
+        technology: Option<&str>,
+    ) -> ModelRelationship {
+        let relationship = ModelRelationship::new(self, target, description, technology);
+        self.outgoing.push(relationship.clone());
+        relationship
+    }
+    /// Relationships recorded with [`uses`](Self::uses) and not yet drained.
+    pub fn outgoing_relationships(&self) -> &[ModelRelationship] {
+        &self.outgoing
+    }
+    /// Removes and returns the recorded relationships.
+    pub fn drain_relationships(&mut self) -> Vec<ModelRelationship> {
+        std::mem::take(&mut self.outgoing)
+    }
+    /// Compares two persons field by field, ignoring their identifiers.
+    pub fn structural_eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.description == other.description
+            && self.location == other.location
+            && self.technology == other.technology
+            && self.tags == other.tags
+    }
+}
 
 #[derive(Debug, thiserror::Error)]
 pub enum PersonError {
@@ -47,11 +94,41 @@ pub enum PersonError {
 pub struct SoftwareSystem {
     #[builder(field)]
     containers: Vec<Container>,
+    /// Set by `without_description()`; lets `build()` accept an empty
+    /// description.
+    #[builder(field)]
+    description_optional: bool,
     name: String,
     description: String,
     #[builder(skip = ElementId::from_name(&name))]
     id: ElementId,
     location: Option<Location>,
+    /// User-defined tags, in addition to the implicit `External` tag for
+    /// external systems.
+    #[builder(default)]
+    tags: Vec<String>,
+    #[builder(skip)]
+    comment: Option<String>,
+    /// Relationships recorded with [`uses`](SoftwareSystem::uses), drained
+    /// by serializers when the system is added.
+    #[builder(skip)]
+    outgoing: Vec<ModelRelationship>,
+}
+
+impl_str_setters!(SoftwareSystemBuilder, software_system_builder);
+
+impl<S: software_system_builder::State> SoftwareSystemBuilder<S> {
+    /// Builds the system without a description, which is then omitted from
+    /// generated output.
+    pub fn without_description(
+        mut self,
+    ) -> SoftwareSystemBuilder<software_system_builder::SetDescription<S>>
+    where
+        S::Description: software_system_builder::IsUnset,
+    {
+        self.description_optional = true;
+        self.description(String::new())
+    }
 }
 
 impl<S: software_system_builder::IsComplete> SoftwareSystemBuilder<S> {
@@ -63,7 +140,9 @@ impl<S: software_system_builder::IsComplete> SoftwareSystemBuilder<S> {
         let system = self.build_internal();
         validate_non_empty(&system.name, "name")?;
         validate_max_length(&system.name, MAX_NAME_LENGTH, "name")?;
-        validate_non_empty(&system.description, "description")?;
+        if !system.description_optional {
+            validate_non_empty(&system.description, "description")?;
+        }
         validate_max_length(&system.description, MAX_DESCRIPTION_LENGTH, "description")?;
         Ok(system)
     }
@@ -76,9 +155,74 @@ impl SoftwareSystem {
     pub fn add_container(&mut self, container: &Container) {
         self.containers.push(container.clone());
     }
+    /// Returns a copy of the system without its containers. The copy keeps
+    /// the system's identifier, so existing relationships still resolve.
+    pub fn without_containers(&self) -> Self {
+        Self {
+            containers: Vec::new(),
+            ..self.clone()
+        }
+    }
+    /// Records a relationship from this system to `target` and returns it.
+    /// Serializers pick up recorded relationships when the system is added.
+    pub fn uses(
+        &mut self,
+        target: &impl Element,
+        description: &str,
+        technology: Option<&str>,
+    ) -> ModelRelationship {
+        let relationship = ModelRelationship::new(self, target, description, technology);
+        self.outgoing.push(relationship.clone());
+        relationship
+    }
+    /// Relationships recorded with [`uses`](Self::uses) and not yet drained.
+    pub fn outgoing_relationships(&self) -> &[ModelRelationship] {
+        &self.outgoing
+    }
+    /// Removes and returns the recorded relationships.
+    pub fn drain_relationships(&mut self) -> Vec<ModelRelationship> {
+        std::mem::take(&mut self.outgoing)
+    }
+    /// Number of containers plus all of their components and code elements.
+    pub fn descendant_count(&self) -> usize {
+        self.containers
+            .iter()
+            .map(|c| 1 + c.descendant_count())
+            .sum()
+    }
+    /// Returns whether the system has no containers.
+    pub fn is_empty(&self) -> bool {
+        self.containers.is_empty()
+    }
+    /// Removes and returns the first container with the given name.
+    pub fn remove_container(&mut self, name: &str) -> Option<Container> {
+        let index = self.containers.iter().position(|c| c.name() == name)?;
+        Some(self.containers.remove(index))
+    }
+    /// Returns the first container with the given name.
+    pub fn container(&self, name: &str) -> Option<&Container> {
+        self.containers.iter().find(|c| c.name() == name)
+    }
+    /// Returns the first container with the given name, mutably.
+    pub fn container_mut(&mut self, name: &str) -> Option<&mut Container> {
+        self.containers.iter_mut().find(|c| c.name() == name)
+    }
+    /// Compares two systems and their containers, ignoring identifiers.
+    pub fn structural_eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.description == other.description
+            && self.location == other.location
+            && self.tags == other.tags
+            && self.containers.len() == other.containers.len()
+            && self
+                .containers
+                .iter()
+                .zip(&other.containers)
+                .all(|(a, b)| a.structural_eq(b))
+    }
 }
 
-impl_element!(SoftwareSystem, ElementType::SoftwareSystem, optional);
+impl_element!(SoftwareSystem, ElementType::SoftwareSystem, optional, tags);
 
 #[derive(Debug, thiserror::Error)]
 pub enum SoftwareSystemError {
@@ -104,6 +248,22 @@ mod tests {
         assert_eq!(p.id().as_str(), "a");
     }
     #[test]
+    fn test_person_builder_from_str() {
+        let p = Person::builder()
+            .with_name("Library Consumer")
+            .with_description("Uses the library")
+            .build()
+            .unwrap();
+        assert_eq!(p.name(), "Library Consumer");
+        assert_eq!(p.description(), "Uses the library");
+
+        let empty = Person::builder()
+            .with_name("")
+            .with_description("B")
+            .build();
+        assert!(empty.is_err());
+    }
+    #[test]
     fn test_person_auto_id_multi_word() {
         let p = Person::builder()
             .name("Library Consumer".into())
@@ -113,6 +273,20 @@ mod tests {
         assert_eq!(p.id().as_str(), "lc");
     }
     #[test]
+    fn test_person_structural_eq() {
+        let build = |description: &str| {
+            Person::builder()
+                .name("User".into())
+                .description(description.into())
+                .build()
+                .unwrap()
+        };
+        let (a, b) = (build("A user"), build("A user"));
+        assert_ne!(a, b);
+        assert!(a.structural_eq(&b));
+        assert!(!a.structural_eq(&build("Another user")));
+    }
+    #[test]
     fn test_person_empty_name() {
         assert!(
             Person::builder()
@@ -151,4 +325,145 @@ mod tests {
             .unwrap();
         assert_eq!(s.id().as_str(), "mcs");
     }
+    #[test]
+    fn test_remove_container() {
+        let web = Container::builder()
+            .name("Web".into())
+            .description("Frontend".into())
+            .container_type(crate::c4::ContainerType::WebApplication)
+            .build()
+            .unwrap();
+        let db = Container::builder()
+            .name("DB".into())
+            .description("Database".into())
+            .container_type(crate::c4::ContainerType::Database)
+            .build()
+            .unwrap();
+        let mut s = SoftwareSystem::builder()
+            .name("S".into())
+            .description("D".into())
+            .add_container(&web)
+            .add_container(&db)
+            .build()
+            .unwrap();
+        let removed = s.remove_container("Web").unwrap();
+        assert_eq!(removed.id(), web.id());
+        assert!(s.container_mut("Web").is_none());
+        assert!(s.remove_container("Web").is_none());
+        assert_eq!(s.containers().len(), 1);
+        assert!(s.container_mut("DB").is_some());
+    }
+    #[test]
+    fn test_navigate_to_nested_component_by_name() {
+        use super::super::code::CodeElement;
+        use super::super::component::Component;
+        use super::super::element::{CodeType, ContainerType};
+        let first = Component::builder()
+            .name("Handler".into())
+            .description("First".into())
+            .build()
+            .unwrap();
+        let second = Component::builder()
+            .name("Handler".into())
+            .description("Second".into())
+            .build()
+            .unwrap();
+        let api = Container::builder()
+            .name("API".into())
+            .description("Backend".into())
+            .container_type(ContainerType::Api)
+            .add_component(&first)
+            .add_component(&second)
+            .build()
+            .unwrap();
+        let mut s = SoftwareSystem::builder()
+            .name("Shop".into())
+            .description("D".into())
+            .add_container(&api)
+            .build()
+            .unwrap();
+
+        let handler = s.container("API").and_then(|c| c.component("Handler"));
+        assert_eq!(handler.map(|c| c.description()), Some("First"));
+        assert!(s.container("Web").is_none());
+        assert!(s.container("API").unwrap().component("Missing").is_none());
+
+        let code = CodeElement::builder()
+            .name("handle".into())
+            .description("Entry point".into())
+            .code_type(CodeType::Function)
+            .build()
+            .unwrap();
+        s.container_mut("API")
+            .and_then(|c| c.component_mut("Handler"))
+            .unwrap()
+            .add_code_element(&code);
+        assert_eq!(s.containers()[0].components()[0].code_elements().len(), 1);
+        assert!(s.containers()[0].components()[1].code_elements().is_empty());
+    }
+    #[test]
+    fn test_descendant_count() {
+        use super::super::code::CodeElement;
+        use super::super::component::Component;
+        use super::super::element::{CodeType, ContainerType};
+        let code = CodeElement::builder()
+            .name("handle".into())
+            .description("Entry point".into())
+            .code_type(CodeType::Function)
+            .build()
+            .unwrap();
+        let handler = Component::builder()
+            .name("Handler".into())
+            .description("Handles requests".into())
+            .add_code_element(&code)
+            .build()
+            .unwrap();
+        let repository = Component::builder()
+            .name("Repository".into())
+            .description("Stores orders".into())
+            .build()
+            .unwrap();
+        let api = Container::builder()
+            .name("API".into())
+            .description("Backend".into())
+            .container_type(ContainerType::Api)
+            .add_component(&handler)
+            .add_component(&repository)
+            .build()
+            .unwrap();
+        let db = Container::builder()
+            .name("DB".into())
+            .description("Storage".into())
+            .container_type(ContainerType::Database)
+            .build()
+            .unwrap();
+        let s = SoftwareSystem::builder()
+            .name("Shop".into())
+            .description("D".into())
+            .add_container(&api)
+            .add_container(&db)
+            .build()
+            .unwrap();
+
+        assert_eq!(api.descendant_count(), 3);
+        assert_eq!(db.descendant_count(), 0);
+        assert!(db.is_empty());
+        assert!(!api.is_empty());
+        assert_eq!(s.descendant_count(), 5);
+        assert!(!s.is_empty());
+        assert!(s.without_containers().is_empty());
+    }
+    #[test]
+    fn test_with_identifier_keeps_element_id_equality() {
+        let s = SoftwareSystem::builder()
+            .name("My Cool System".into())
+            .description("D".into())
+            .build()
+            .unwrap();
+        let pinned = s.clone().with_identifier("cool");
+        assert_eq!(pinned.id().as_str(), "cool");
+        assert!(pinned.id().is_explicit());
+        assert!(!s.id().is_explicit());
+        assert_eq!(pinned.id(), s.id());
+    }
 }
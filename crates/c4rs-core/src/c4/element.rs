@@ -11,11 +11,13 @@ static NEXT_ID: AtomicU64 = AtomicU64::new(1);
 ///
 /// Users cannot construct this directly — it is created automatically when
 /// building an element via its builder. Use `element.id()` to obtain a
-/// reference for passing into relationship declarations.
+/// reference for passing into relationship declarations. The slug can be
+/// pinned with the element's `with_identifier` method.
 #[derive(Debug, Clone)]
 pub struct ElementId {
     slug: String,
     seq: u64,
+    explicit: bool,
 }
 
 impl ElementId {
@@ -33,12 +35,43 @@ impl ElementId {
                 })
                 .collect(),
             seq: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            explicit: false,
+        }
+    }
+
+    /// Returns a copy of this id with its slug pinned to `slug`. The internal
+    /// sequence number is kept, so existing references stay valid.
+    ///
+    /// The slug is normalized to a valid DSL identifier once, here, so the
+    /// element is declared and referenced under the same name: characters
+    /// other than letters, digits, and `_` become `_`, and a leading digit
+    /// gets a `_` prefix (`api-gateway` → `api_gateway`, `123` → `_123`).
+    pub(crate) fn pinned(&self, slug: &str) -> Self {
+        Self {
+            slug: normalize_identifier(slug),
+            seq: self.seq,
+            explicit: true,
         }
     }
 
     pub fn as_str(&self) -> &str {
         &self.slug
     }
+
+    /// Returns whether the slug was pinned explicitly rather than derived
+    /// from the element name.
+    pub fn is_explicit(&self) -> bool {
+        self.explicit
+    }
+}
+
+fn normalize_identifier(slug: &str) -> String {
+    let normalized = slug.replace(|c: char| !c.is_alphanumeric() && c != '_', "_");
+    match normalized.chars().next() {
+        None => "element".to_string(),
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => normalized,
+        Some(_) => format!("_{}", normalized),
+    }
 }
 
 impl PartialEq for ElementId {
@@ -67,9 +100,17 @@ pub trait Element {
     fn description(&self) -> &str;
     fn element_type(&self) -> ElementType;
     fn location(&self) -> Location;
+    /// User-defined tags. Persons, software systems, containers, and
+    /// components carry their own; code elements, which are written as
+    /// properties of their component rather than as DSL elements, have none.
+    fn tags(&self) -> &[String] {
+        &[]
+    }
 }
 
+/// Serializes as the Structurizr element type name, e.g. `"SoftwareSystem"`.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ElementType {
     Person,
     SoftwareSystem,
@@ -106,33 +147,72 @@ impl fmt::Display for Location {
     }
 }
 
+/// Whether a component is part of its container's public API or an
+/// internal implementation detail. Displays as the tag emitted for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ComponentVisibility {
+    Public,
+    Internal,
+}
+
+impl fmt::Display for ComponentVisibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComponentVisibility::Public => write!(f, "Public API"),
+            ComponentVisibility::Internal => write!(f, "Internal"),
+        }
+    }
+}
+
+/// Serializes as its display name (e.g. `"Web Application"`); `Other` is
+/// externally tagged as `{"Other": "..."}`. This form is stable.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ContainerType {
+    #[cfg_attr(feature = "serde", serde(rename = "Web Application"))]
     WebApplication,
+    #[cfg_attr(feature = "serde", serde(rename = "Desktop Application"))]
     DesktopApplication,
+    #[cfg_attr(feature = "serde", serde(rename = "Mobile Application"))]
     MobileApplication,
     Database,
+    #[cfg_attr(feature = "serde", serde(rename = "File System"))]
     FileSystem,
+    #[cfg_attr(feature = "serde", serde(rename = "API"))]
     Api,
+    #[cfg_attr(feature = "serde", serde(rename = "Message Bus"))]
     MessageBus,
+    /// A serverless function such as an AWS Lambda or Cloud Function.
+    Function,
     Other(String),
 }
 
-impl fmt::Display for ContainerType {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl ContainerType {
+    /// The Structurizr tag for this container type, e.g. `"Web Application"`.
+    /// This is the single mapping used wherever a container type is written
+    /// or read back as a tag; `Other` returns its own text.
+    pub fn as_structurizr_tag(&self) -> String {
         match self {
-            ContainerType::WebApplication => write!(f, "Web Application"),
-            ContainerType::DesktopApplication => write!(f, "Desktop Application"),
-            ContainerType::MobileApplication => write!(f, "Mobile Application"),
-            ContainerType::Database => write!(f, "Database"),
-            ContainerType::FileSystem => write!(f, "File System"),
-            ContainerType::Api => write!(f, "API"),
-            ContainerType::MessageBus => write!(f, "Message Bus"),
-            ContainerType::Other(s) => write!(f, "{}", s),
+            ContainerType::WebApplication => "Web Application".to_string(),
+            ContainerType::DesktopApplication => "Desktop Application".to_string(),
+            ContainerType::MobileApplication => "Mobile Application".to_string(),
+            ContainerType::Database => "Database".to_string(),
+            ContainerType::FileSystem => "File System".to_string(),
+            ContainerType::Api => "API".to_string(),
+            ContainerType::MessageBus => "Message Bus".to_string(),
+            ContainerType::Function => "Function".to_string(),
+            ContainerType::Other(s) => s.clone(),
         }
     }
 }
 
+impl fmt::Display for ContainerType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.as_structurizr_tag())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum InteractionStyle {
     #[default]
@@ -151,7 +231,10 @@ impl fmt::Display for InteractionStyle {
     }
 }
 
+/// Serializes as the variant name (e.g. `"Struct"`); `Other` is externally
+/// tagged as `{"Other": "..."}`, like [`ContainerType`].
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CodeType {
     Class,
     Struct,
@@ -159,17 +242,130 @@ pub enum CodeType {
     Trait,
     Module,
     Enum,
+    /// Any other kind of code element, such as a macro or type alias.
+    Other(String),
+}
+
+impl CodeType {
+    /// The tag text for this code type; `Other` returns its own text.
+    pub fn as_tag(&self) -> String {
+        match self {
+            CodeType::Class => "Class".to_string(),
+            CodeType::Struct => "Struct".to_string(),
+            CodeType::Function => "Function".to_string(),
+            CodeType::Trait => "Trait".to_string(),
+            CodeType::Module => "Module".to_string(),
+            CodeType::Enum => "Enum".to_string(),
+            CodeType::Other(s) => s.clone(),
+        }
+    }
 }
 
 impl fmt::Display for CodeType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            CodeType::Class => write!(f, "Class"),
-            CodeType::Struct => write!(f, "Struct"),
-            CodeType::Function => write!(f, "Function"),
-            CodeType::Trait => write!(f, "Trait"),
-            CodeType::Module => write!(f, "Module"),
-            CodeType::Enum => write!(f, "Enum"),
+        f.write_str(&self.as_tag())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_pinned_slug_is_normalized() {
+        let id = ElementId::from_name("API Gateway");
+        assert_eq!(id.pinned("api-gateway").as_str(), "api_gateway");
+        assert_eq!(id.pinned("123").as_str(), "_123");
+        assert_eq!(id.pinned("").as_str(), "element");
+        assert_eq!(id.pinned("api_gateway").as_str(), "api_gateway");
+        assert_eq!(id.pinned("api-gateway"), id);
+    }
+    #[test]
+    fn test_container_type_function_display() {
+        assert_eq!(ContainerType::Function.to_string(), "Function");
+    }
+    #[test]
+    fn test_container_type_structurizr_tags() {
+        let cases = [
+            (ContainerType::WebApplication, "Web Application"),
+            (ContainerType::DesktopApplication, "Desktop Application"),
+            (ContainerType::MobileApplication, "Mobile Application"),
+            (ContainerType::Database, "Database"),
+            (ContainerType::FileSystem, "File System"),
+            (ContainerType::Api, "API"),
+            (ContainerType::MessageBus, "Message Bus"),
+            (ContainerType::Function, "Function"),
+            (ContainerType::Other("Queue".into()), "Queue"),
+        ];
+        for (container_type, tag) in cases {
+            assert_eq!(container_type.as_structurizr_tag(), tag);
+            assert_eq!(container_type.to_string(), tag);
+        }
+    }
+    #[test]
+    fn test_code_type_other_tag() {
+        assert_eq!(CodeType::Other("macro".into()).as_tag(), "macro");
+        assert_eq!(CodeType::Other("macro".into()).to_string(), "macro");
+        assert_eq!(CodeType::Struct.as_tag(), "Struct");
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_code_type_serde_round_trip() {
+        for (code_type, json) in [
+            (CodeType::Trait, r#""Trait""#),
+            (CodeType::Other("macro".into()), r#"{"Other":"macro"}"#),
+        ] {
+            assert_eq!(serde_json::to_string(&code_type).unwrap(), json);
+            assert_eq!(serde_json::from_str::<CodeType>(json).unwrap(), code_type);
+        }
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_container_type_function_serde_round_trip() {
+        let json = serde_json::to_string(&ContainerType::Function).unwrap();
+        let parsed: ContainerType = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, ContainerType::Function);
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_container_type_serde_strings() {
+        let cases = [
+            (ContainerType::WebApplication, r#""Web Application""#),
+            (
+                ContainerType::DesktopApplication,
+                r#""Desktop Application""#,
+            ),
+            (ContainerType::MobileApplication, r#""Mobile Application""#),
+            (ContainerType::Database, r#""Database""#),
+            (ContainerType::FileSystem, r#""File System""#),
+            (ContainerType::Api, r#""API""#),
+            (ContainerType::MessageBus, r#""Message Bus""#),
+            (ContainerType::Function, r#""Function""#),
+            (ContainerType::Other("Queue".into()), r#"{"Other":"Queue"}"#),
+        ];
+        for (container_type, json) in cases {
+            assert_eq!(serde_json::to_string(&container_type).unwrap(), json);
+            assert_eq!(
+                serde_json::from_str::<ContainerType>(json).unwrap(),
+                container_type
+            );
+        }
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_element_type_serde_strings() {
+        let cases = [
+            (ElementType::Person, r#""Person""#),
+            (ElementType::SoftwareSystem, r#""SoftwareSystem""#),
+            (ElementType::Container, r#""Container""#),
+            (ElementType::Component, r#""Component""#),
+            (ElementType::Code, r#""Code""#),
+        ];
+        for (element_type, json) in cases {
+            assert_eq!(serde_json::to_string(&element_type).unwrap(), json);
+            assert_eq!(
+                serde_json::from_str::<ElementType>(json).unwrap(),
+                element_type
+            );
         }
     }
 }
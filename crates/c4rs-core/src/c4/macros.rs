@@ -1,7 +1,11 @@
+/// Implements [`Element`](crate::c4::Element) and the common accessors for
+/// an element struct. An optional trailing field name, as in
+/// `impl_element!(Person, ElementType::Person, optional, tags)`, backs
+/// `Element::tags` and an inherent `tags()` accessor.
 #[macro_export]
 macro_rules! impl_element {
     // Variant 1: Fixed location (Location::Internal)
-    ($struct:ident, $type_variant:expr, fixed) => {
+    ($struct:ident, $type_variant:expr, fixed $(, $tags:ident)?) => {
         impl $crate::c4::Element for $struct {
             fn id(&self) -> &$crate::c4::ElementId {
                 &self.id
@@ -18,11 +22,38 @@ macro_rules! impl_element {
             fn location(&self) -> $crate::c4::Location {
                 $crate::c4::Location::Internal
             }
+            $(
+                fn tags(&self) -> &[String] {
+                    &self.$tags
+                }
+            )?
         }
         impl $struct {
             pub fn id(&self) -> &$crate::c4::ElementId {
                 &self.id
             }
+            /// Pins the DSL identifier instead of deriving it from the name.
+            /// Invalid characters are replaced, e.g. `api-gateway` becomes
+            /// `api_gateway`.
+            pub fn with_identifier(mut self, identifier: &str) -> Self {
+                self.id = self.id.pinned(identifier);
+                self
+            }
+            /// Attaches a comment written directly above the element in
+            /// generated output.
+            pub fn with_comment(mut self, comment: &str) -> Self {
+                self.comment = Some(comment.to_string());
+                self
+            }
+            pub fn comment(&self) -> Option<&str> {
+                self.comment.as_deref()
+            }
+            $(
+                /// User-defined tags.
+                pub fn tags(&self) -> &[String] {
+                    &self.$tags
+                }
+            )?
             pub fn name(&self) -> &str {
                 &self.name
             }
@@ -35,7 +66,7 @@ macro_rules! impl_element {
         }
     };
     // Variant 2: Optional location (from self.location field)
-    ($struct:ident, $type_variant:expr, optional) => {
+    ($struct:ident, $type_variant:expr, optional $(, $tags:ident)?) => {
         impl $crate::c4::Element for $struct {
             fn id(&self) -> &$crate::c4::ElementId {
                 &self.id
@@ -54,11 +85,38 @@ macro_rules! impl_element {
                     .clone()
                     .unwrap_or($crate::c4::Location::Internal)
             }
+            $(
+                fn tags(&self) -> &[String] {
+                    &self.$tags
+                }
+            )?
         }
         impl $struct {
             pub fn id(&self) -> &$crate::c4::ElementId {
                 &self.id
             }
+            /// Pins the DSL identifier instead of deriving it from the name.
+            /// Invalid characters are replaced, e.g. `api-gateway` becomes
+            /// `api_gateway`.
+            pub fn with_identifier(mut self, identifier: &str) -> Self {
+                self.id = self.id.pinned(identifier);
+                self
+            }
+            /// Attaches a comment written directly above the element in
+            /// generated output.
+            pub fn with_comment(mut self, comment: &str) -> Self {
+                self.comment = Some(comment.to_string());
+                self
+            }
+            pub fn comment(&self) -> Option<&str> {
+                self.comment.as_deref()
+            }
+            $(
+                /// User-defined tags.
+                pub fn tags(&self) -> &[String] {
+                    &self.$tags
+                }
+            )?
             pub fn name(&self) -> &str {
                 &self.name
             }
@@ -79,3 +137,28 @@ macro_rules! impl_element {
 }
 
 pub use impl_element;
+
+/// Adds `with_name`/`with_description` setters taking `&str` to an element
+/// builder, so callers can skip `.into()`. Validation still happens in
+/// `build()`.
+#[macro_export]
+macro_rules! impl_str_setters {
+    ($builder:ident, $state:ident) => {
+        impl<S: $state::State> $builder<S> {
+            pub fn with_name(self, name: &str) -> $builder<$state::SetName<S>>
+            where
+                S::Name: $state::IsUnset,
+            {
+                self.name(name.to_string())
+            }
+            pub fn with_description(self, description: &str) -> $builder<$state::SetDescription<S>>
+            where
+                S::Description: $state::IsUnset,
+            {
+                self.description(description.to_string())
+            }
+        }
+    };
+}
+
+pub use impl_str_setters;
@@ -6,6 +6,8 @@ pub mod container;
 pub mod context;
 pub mod element;
 pub mod macros;
+pub mod model;
+pub mod model_builder;
 pub mod relationship;
 
 pub use code::CodeElement;
@@ -13,6 +15,11 @@ pub use component::{Component, ComponentError};
 pub use container::{Container, ContainerError};
 pub use context::{Person, PersonError, SoftwareSystem, SoftwareSystemError};
 pub use element::{
-    CodeType, ContainerType, Element, ElementId, ElementType, InteractionStyle, Location,
+    CodeType, ComponentVisibility, ContainerType, Element, ElementId, ElementType,
+    InteractionStyle, Location,
+};
+pub use model::{ModelChange, ModelDiff, ModelRelationship, WorkspaceModel, diff};
+pub use model_builder::{ModelBuilder, ModelBuilderError};
+pub use relationship::{
+    Relationship, RelationshipError, RelationshipTechnology, create_relationship,
 };
-pub use relationship::{Relationship, RelationshipError, create_relationship};
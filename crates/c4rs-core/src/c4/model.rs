@@ -0,0 +1,394 @@
+use super::context::{Person, SoftwareSystem};
+use super::element::{Element, ElementId, ElementType};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// A complete C4 model: top-level persons and software systems (with their
+/// nested containers, components, and code elements) plus the relationships
+/// between any of them.
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceModel {
+    persons: Vec<Person>,
+    software_systems: Vec<SoftwareSystem>,
+    relationships: Vec<ModelRelationship>,
+}
+
+/// A relationship stored in a [`WorkspaceModel`], referring to its
+/// endpoints by id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelRelationship {
+    source: ElementId,
+    target: ElementId,
+    description: String,
+    technology: Option<String>,
+}
+
+impl ModelRelationship {
+    pub(crate) fn new(
+        source: &impl Element,
+        target: &impl Element,
+        description: &str,
+        technology: Option<&str>,
+    ) -> Self {
+        Self {
+            source: source.id().clone(),
+            target: target.id().clone(),
+            description: description.to_string(),
+            technology: technology.map(str::to_string),
+        }
+    }
+    pub fn source(&self) -> &ElementId {
+        &self.source
+    }
+    pub fn target(&self) -> &ElementId {
+        &self.target
+    }
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+    pub fn technology(&self) -> Option<&str> {
+        self.technology.as_deref()
+    }
+}
+
+impl WorkspaceModel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn persons(&self) -> &[Person] {
+        &self.persons
+    }
+    pub fn software_systems(&self) -> &[SoftwareSystem] {
+        &self.software_systems
+    }
+    pub fn relationships(&self) -> &[ModelRelationship] {
+        &self.relationships
+    }
+    pub fn add_person(&mut self, person: &Person) {
+        self.persons.push(person.clone());
+    }
+    pub fn add_software_system(&mut self, system: &SoftwareSystem) {
+        self.software_systems.push(system.clone());
+    }
+    pub fn add_relationship(
+        &mut self,
+        source: &impl Element,
+        target: &impl Element,
+        description: &str,
+        technology: Option<&str>,
+    ) {
+        self.relationships.push(ModelRelationship::new(
+            source,
+            target,
+            description,
+            technology,
+        ));
+    }
+
+    /// Maps every element's type and name path (e.g.
+    /// `Component Shop/API/Handler`) to a summary of its own fields,
+    /// excluding children. The type keeps a person and a software system
+    /// with the same name apart.
+    fn element_summaries(&self) -> BTreeMap<String, (ElementId, String)> {
+        let key = |element_type: ElementType, path: &str| format!("{} {}", element_type, path);
+        let mut summaries = BTreeMap::new();
+        for person in &self.persons {
+            let summary = format!(
+                "{}|{:?}|{:?}|{:?}",
+                person.description(),
+                person.location(),
+                person.technology(),
+                person.tags()
+            );
+            summaries.insert(
+                key(ElementType::Person, person.name()),
+                (person.id().clone(), summary),
+            );
+        }
+        for system in &self.software_systems {
+            let system_path = system.name().to_string();
+            for container in system.containers() {
+                let container_path = format!("{}/{}", system_path, container.name());
+                for component in container.components() {
+                    let component_path = format!("{}/{}", container_path, component.name());
+                    for code in component.code_elements() {
+                        let summary = format!(
+                            "{}|{:?}|{:?}|{:?}",
+                            code.description(),
+                            code.code_type(),
+                            code.language(),
+                            code.file_path()
+                        );
+                        summaries.insert(
+                            key(
+                                ElementType::Code,
+                                &format!("{}/{}", component_path, code.name()),
+                            ),
+                            (code.id().clone(), summary),
+                        );
+                    }
+                    let summary = format!(
+                        "{}|{:?}|{:?}|{:?}|{:?}",
+                        component.description(),
+                        component.technologies(),
+                        component.responsibilities(),
+                        component.visibility(),
+                        component.tags()
+                    );
+                    summaries.insert(
+                        key(ElementType::Component, &component_path),
+                        (component.id().clone(), summary),
+                    );
+                }
+                let summary = format!(
+                    "{}|{:?}|{:?}|{:?}|{:?}",
+                    container.description(),
+                    container.container_type(),
+                    container.technologies(),
+                    container.tags(),
+                    container.instances()
+                );
+                summaries.insert(
+                    key(ElementType::Container, &container_path),
+                    (container.id().clone(), summary),
+                );
+            }
+            let summary = format!(
+                "{}|{:?}|{:?}",
+                system.description(),
+                system.location(),
+                system.tags()
+            );
+            summaries.insert(
+                key(ElementType::SoftwareSystem, &system_path),
+                (system.id().clone(), summary),
+            );
+        }
+        summaries
+    }
+
+    /// Maps each relationship's `source -> target: description` to its
+    /// technology, so several relationships between the same elements are
+    /// tracked separately. Exact repeats get a ` (2)`, ` (3)`, ... suffix.
+    /// Relationships to unknown elements are keyed by the endpoint's
+    /// identifier.
+    fn relationship_summaries(
+        &self,
+        paths: &HashMap<&ElementId, &str>,
+    ) -> BTreeMap<String, String> {
+        let path = |id: &ElementId| paths.get(id).copied().unwrap_or(id.as_str()).to_string();
+        let mut summaries = BTreeMap::new();
+        for rel in &self.relationships {
+            let base = format!(
+                "{} -> {}: {}",
+                path(&rel.source),
+                path(&rel.target),
+                rel.description
+            );
+            let mut key = base.clone();
+            let mut count = 1;
+            while summaries.contains_key(&key) {
+                count += 1;
+                key = format!("{} ({})", base, count);
+            }
+            summaries.insert(key, format!("{:?}", rel.technology));
+        }
+        summaries
+    }
+}
+
+/// A single difference between two models, identified by element type and
+/// name path (e.g. `Container Shop/API`) or by `source -> target: description`
+/// for relationships.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelChange {
+    Added(String),
+    Removed(String),
+    Modified(String),
+}
+
+impl fmt::Display for ModelChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModelChange::Added(path) => write!(f, "+ {}", path),
+            ModelChange::Removed(path) => write!(f, "- {}", path),
+            ModelChange::Modified(path) => write!(f, "~ {}", path),
+        }
+    }
+}
+
+/// Differences between two models, as returned by [`diff`]. Prints one
+/// change per line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelDiff {
+    pub elements: Vec<ModelChange>,
+    pub relationships: Vec<ModelChange>,
+}
+
+impl ModelDiff {
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty() && self.relationships.is_empty()
+    }
+}
+
+impl fmt::Display for ModelDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in self.elements.iter().chain(&self.relationships) {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares two models by element type and name path. An element is
+/// modified when its own fields differ; changes to its children are reported
+/// separately. Relationships are matched by endpoints and description, and
+/// are modified when their technology differs.
+pub fn diff(old: &WorkspaceModel, new: &WorkspaceModel) -> ModelDiff {
+    let old_elements = old.element_summaries();
+    let new_elements = new.element_summaries();
+    let old_relationships = old.relationship_summaries(&id_paths(&old_elements));
+    let new_relationships = new.relationship_summaries(&id_paths(&new_elements));
+    ModelDiff {
+        elements: changes(&summaries(&old_elements), &summaries(&new_elements)),
+        relationships: changes(&old_relationships, &new_relationships),
+    }
+}
+
+fn id_paths(elements: &BTreeMap<String, (ElementId, String)>) -> HashMap<&ElementId, &str> {
+    elements
+        .iter()
+        .map(|(path, (id, _))| (id, path.as_str()))
+        .collect()
+}
+
+fn summaries(elements: &BTreeMap<String, (ElementId, String)>) -> BTreeMap<String, String> {
+    elements
+        .iter()
+        .map(|(path, (_, summary))| (path.clone(), summary.clone()))
+        .collect()
+}
+
+fn changes(old: &BTreeMap<String, String>, new: &BTreeMap<String, String>) -> Vec<ModelChange> {
+    let mut changes = Vec::new();
+    for (key, summary) in new {
+        match old.get(key) {
+            None => changes.push(ModelChange::Added(key.clone())),
+            Some(previous) if previous != summary => {
+                changes.push(ModelChange::Modified(key.clone()))
+            }
+            Some(_) => {}
+        }
+    }
+    for key in old.keys().filter(|key| !new.contains_key(*key)) {
+        changes.push(ModelChange::Removed(key.clone()));
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::c4::{Container, ContainerType};
+
+    fn container(name: &str) -> Container {
+        Container::builder()
+            .name(name.into())
+            .description("D".into())
+            .container_type(ContainerType::Api)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_diff_added_container() {
+        let user = Person::builder()
+            .name("User".into())
+            .description("A user".into())
+            .build()
+            .unwrap();
+        let api = container("API");
+        let shop = |containers: &[&Container]| {
+            let mut builder = SoftwareSystem::builder()
+                .name("Shop".into())
+                .description("Online shop".into());
+            for c in containers {
+                builder = builder.add_container(c);
+            }
+            builder.build().unwrap()
+        };
+
+        let mut old = WorkspaceModel::new();
+        old.add_person(&user);
+        let old_shop = shop(&[&api]);
+        old.add_software_system(&old_shop);
+        old.add_relationship(&user, &old_shop, "Uses", None);
+
+        let mut new = WorkspaceModel::new();
+        new.add_person(&user);
+        let new_shop = shop(&[&api, &container("Worker")]);
+        new.add_software_system(&new_shop);
+        new.add_relationship(&user, &new_shop, "Uses", None);
+
+        let changes = diff(&old, &new);
+        assert_eq!(
+            changes.elements,
+            vec![ModelChange::Added("Container Shop/Worker".to_string())]
+        );
+        assert!(changes.relationships.is_empty());
+        assert_eq!(changes.to_string(), "+ Container Shop/Worker\n");
+        assert!(diff(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_diff_keeps_same_named_elements_and_parallel_relationships() {
+        let admin = Person::builder()
+            .name("Admin".into())
+            .description("An administrator".into())
+            .build()
+            .unwrap();
+        let admin_system = SoftwareSystem::builder()
+            .name("Admin".into())
+            .description("Back office".into())
+            .build()
+            .unwrap();
+        let model = |technology: &str, instances: u32| {
+            let api = Container::builder()
+                .name("API".into())
+                .description("D".into())
+                .container_type(ContainerType::Api)
+                .instances(instances)
+                .build()
+                .unwrap();
+            let system = SoftwareSystem::builder()
+                .name("Shop".into())
+                .description("Online shop".into())
+                .add_container(&api)
+                .build()
+                .unwrap();
+            let mut model = WorkspaceModel::new();
+            model.add_person(&admin);
+            model.add_software_system(&admin_system);
+            model.add_software_system(&system);
+            model.add_relationship(&admin, &admin_system, "Reads", Some("HTTP"));
+            model.add_relationship(&admin, &admin_system, "Writes", Some(technology));
+            model
+        };
+
+        let old = model("HTTP", 1);
+        assert_eq!(old.element_summaries().len(), 4);
+        assert_eq!(old.relationship_summaries(&HashMap::new()).len(), 2);
+
+        let changes = diff(&old, &model("gRPC", 3));
+        assert_eq!(
+            changes.elements,
+            vec![ModelChange::Modified("Container Shop/API".to_string())]
+        );
+        assert_eq!(
+            changes.relationships,
+            vec![ModelChange::Modified(
+                "Person Admin -> SoftwareSystem Admin: Writes".to_string()
+            )]
+        );
+    }
+}
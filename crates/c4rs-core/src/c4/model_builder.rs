@@ -0,0 +1,205 @@
+use super::component::{Component, ComponentError};
+use super::container::{Container, ContainerError};
+use super::context::{Person, PersonError, SoftwareSystem, SoftwareSystemError};
+use super::element::ContainerType;
+use super::model::WorkspaceModel;
+
+/// Flat builder for a [`WorkspaceModel`]. Each call adds an element to the
+/// current parent: containers go into the last system, components into the
+/// last container. Adding a person or system starts a new top-level element.
+///
+/// Errors are deferred: the first failure is kept and returned by
+/// [`build`](Self::build), and later calls are ignored.
+#[derive(Debug, Default)]
+pub struct ModelBuilder {
+    model: WorkspaceModel,
+    system: Option<SoftwareSystem>,
+    container: Option<Container>,
+    error: Option<ModelBuilderError>,
+}
+
+impl ModelBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn person(mut self, name: &str, description: &str) -> Self {
+        if self.error.is_none() {
+            self.close_system();
+            let person = Person::builder()
+                .name(name.to_string())
+                .description(description.to_string())
+                .build();
+            match person {
+                Ok(person) => self.model.add_person(&person),
+                Err(err) => self.error = Some(err.into()),
+            }
+        }
+        self
+    }
+
+    /// Starts a new software system; following containers are added to it.
+    pub fn system(mut self, name: &str, description: &str) -> Self {
+        if self.error.is_none() {
+            self.close_system();
+            let system = SoftwareSystem::builder()
+                .name(name.to_string())
+                .description(description.to_string())
+                .build();
+            match system {
+                Ok(system) => self.system = Some(system),
+                Err(err) => self.error = Some(err.into()),
+            }
+        }
+        self
+    }
+
+    /// Adds a container to the current system; following components are
+    /// added to it.
+    pub fn container(
+        mut self,
+        name: &str,
+        description: &str,
+        container_type: ContainerType,
+    ) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+        if self.system.is_none() {
+            self.error = Some(ModelBuilderError::MissingParent {
+                element: name.to_string(),
+                parent: "software system",
+            });
+            return self;
+        }
+        self.close_container();
+        let container = Container::builder()
+            .name(name.to_string())
+            .description(description.to_string())
+            .container_type(container_type)
+            .build();
+        match container {
+            Ok(container) => self.container = Some(container),
+            Err(err) => self.error = Some(err.into()),
+        }
+        self
+    }
+
+    /// Adds a component to the current container.
+    pub fn component(mut self, name: &str, description: &str) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+        let Some(container) = self.container.as_mut() else {
+            self.error = Some(ModelBuilderError::MissingParent {
+                element: name.to_string(),
+                parent: "container",
+            });
+            return self;
+        };
+        let component = Component::builder()
+            .name(name.to_string())
+            .description(description.to_string())
+            .build();
+        match component {
+            Ok(component) => container.add_component(&component),
+            Err(err) => self.error = Some(err.into()),
+        }
+        self
+    }
+
+    pub fn build(mut self) -> Result<WorkspaceModel, ModelBuilderError> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        self.close_system();
+        Ok(self.model)
+    }
+
+    fn close_container(&mut self) {
+        if let (Some(container), Some(system)) = (self.container.take(), self.system.as_mut()) {
+            system.add_container(&container);
+        }
+    }
+
+    fn close_system(&mut self) {
+        self.close_container();
+        if let Some(system) = self.system.take() {
+            self.model.add_software_system(&system);
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ModelBuilderError {
+    #[error("{element} must be added inside a {parent}")]
+    MissingParent {
+        element: String,
+        parent: &'static str,
+    },
+    #[error(transparent)]
+    Person(#[from] PersonError),
+    #[error(transparent)]
+    SoftwareSystem(#[from] SoftwareSystemError),
+    #[error(transparent)]
+    Container(#[from] ContainerError),
+    #[error(transparent)]
+    Component(#[from] ComponentError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_builder_tracks_hierarchy() {
+        let model = ModelBuilder::new()
+            .person("User", "A user")
+            .system("Shop", "Online shop")
+            .container("Web", "Frontend", ContainerType::WebApplication)
+            .component("Cart", "Shopping cart")
+            .component("Checkout", "Checkout flow")
+            .container("API", "Backend", ContainerType::Api)
+            .component("Orders", "Order handling")
+            .system("Payments", "Payment provider")
+            .build()
+            .unwrap();
+
+        assert_eq!(model.persons().len(), 1);
+        let systems = model.software_systems();
+        assert_eq!(
+            systems.iter().map(|s| s.name()).collect::<Vec<_>>(),
+            ["Shop", "Payments"]
+        );
+        let containers = systems[0].containers();
+        assert_eq!(
+            containers.iter().map(|c| c.name()).collect::<Vec<_>>(),
+            ["Web", "API"]
+        );
+        assert_eq!(
+            containers[0]
+                .components()
+                .iter()
+                .map(|c| c.name())
+                .collect::<Vec<_>>(),
+            ["Cart", "Checkout"]
+        );
+        assert_eq!(containers[1].components()[0].name(), "Orders");
+        assert!(systems[1].containers().is_empty());
+    }
+
+    #[test]
+    fn test_model_builder_component_without_container() {
+        let result = ModelBuilder::new()
+            .system("Shop", "Online shop")
+            .component("Cart", "Shopping cart")
+            .build();
+        assert!(matches!(
+            result,
+            Err(ModelBuilderError::MissingParent { element, parent: "container" }) if element == "Cart"
+        ));
+
+        let result = ModelBuilder::new().person("", "Nameless").build();
+        assert!(matches!(result, Err(ModelBuilderError::Person(_))));
+    }
+}
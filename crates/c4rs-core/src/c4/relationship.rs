@@ -7,6 +7,7 @@ use super::container::Container;
 use super::context::Person;
 use crate::constants::limits::MAX_TECHNOLOGY_LENGTH;
 use crate::validation::{validate_max_length, validate_non_empty};
+use std::fmt;
 
 /// Relationship between two C4 elements.
 #[derive(Debug, Clone, PartialEq, Eq, Builder)]
@@ -18,6 +19,23 @@ pub struct Relationship<S: Element, T: Element> {
     technology: Option<String>,
     #[builder(default)]
     interaction_style: InteractionStyle,
+    url: Option<String>,
+    #[builder(default)]
+    tags: Vec<String>,
+}
+
+impl<S: Element, T: Element, State: relationship_builder::State> RelationshipBuilder<S, T, State> {
+    /// Sets the technology from its protocol and format, stored as the
+    /// conventional `"Protocol/Format"` string.
+    pub fn structured_technology(
+        self,
+        technology: RelationshipTechnology,
+    ) -> RelationshipBuilder<S, T, relationship_builder::SetTechnology<State>>
+    where
+        State::Technology: relationship_builder::IsUnset,
+    {
+        self.technology(technology.to_string())
+    }
 }
 
 impl<S: Element, T: Element, State: relationship_builder::IsComplete>
@@ -52,9 +70,63 @@ impl<S: Element, T: Element> Relationship<S, T> {
         self.technology.as_deref()
     }
 
+    /// Splits the technology into protocol and format, e.g. `"HTTPS/JSON"`.
+    pub fn technology_parts(&self) -> Option<RelationshipTechnology> {
+        self.technology
+            .as_deref()
+            .map(RelationshipTechnology::parse)
+    }
+
     pub fn interaction_style(&self) -> InteractionStyle {
         self.interaction_style.clone()
     }
+
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+}
+
+/// A relationship technology split into its transport protocol and payload
+/// format. Displays as `"Protocol/Format"`, or whichever part is present.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RelationshipTechnology {
+    pub protocol: Option<String>,
+    pub format: Option<String>,
+}
+
+impl RelationshipTechnology {
+    /// Parses a technology string such as `"HTTPS/JSON"`. Text without a `/`
+    /// is taken as the protocol.
+    pub fn parse(technology: &str) -> Self {
+        let part = |s: &str| {
+            let s = s.trim();
+            (!s.is_empty()).then(|| s.to_string())
+        };
+        match technology.split_once('/') {
+            Some((protocol, format)) => Self {
+                protocol: part(protocol),
+                format: part(format),
+            },
+            None => Self {
+                protocol: part(technology),
+                format: None,
+            },
+        }
+    }
+}
+
+impl fmt::Display for RelationshipTechnology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.protocol, &self.format) {
+            (Some(protocol), Some(format)) => write!(f, "{}/{}", protocol, format),
+            (Some(part), None) | (None, Some(part)) => write!(f, "{}", part),
+            (None, None) => Ok(()),
+        }
+    }
 }
 
 pub fn create_relationship<S: Element, T: Element>(
@@ -118,6 +190,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_structured_technology() {
+        let user = Person::builder()
+            .name("User".into())
+            .description("A user".into())
+            .build()
+            .unwrap();
+        let technology = RelationshipTechnology {
+            protocol: Some("HTTPS".into()),
+            format: Some("JSON".into()),
+        };
+
+        let relationship: Relationship<Person, Person> = Relationship::builder()
+            .source(user.clone())
+            .target(user)
+            .description("Calls".into())
+            .structured_technology(technology.clone())
+            .build()
+            .unwrap();
+
+        assert_eq!(relationship.technology(), Some("HTTPS/JSON"));
+        assert_eq!(relationship.technology_parts(), Some(technology));
+        assert_eq!(
+            RelationshipTechnology::parse("gRPC"),
+            RelationshipTechnology {
+                protocol: Some("gRPC".into()),
+                format: None,
+            }
+        );
+    }
+
     #[test]
     fn test_cross_level_relationship() {
         let person = Person::builder()
@@ -6,3 +6,64 @@ pub mod limits {
     pub const MAX_FILE_PATH_LENGTH: usize = 512;
     pub const MAX_RESPONSIBILITY_LENGTH: usize = 500;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::limits::MAX_TECHNOLOGY_LENGTH;
+    use crate::c4::{Component, Container, ContainerType, Person, Relationship};
+
+    #[test]
+    fn test_technology_limit_is_shared_by_all_element_types() {
+        let person = |technology: String| {
+            Person::builder()
+                .name("User".into())
+                .description("A user".into())
+                .technology(technology)
+                .build()
+                .is_ok()
+        };
+        let container = |technology: String| {
+            Container::builder()
+                .name("API".into())
+                .description("Backend".into())
+                .container_type(ContainerType::Api)
+                .technology(technology)
+                .build()
+                .is_ok()
+        };
+        let component = |technology: String| {
+            Component::builder()
+                .name("Handler".into())
+                .description("Handles requests".into())
+                .technology(technology)
+                .build()
+                .is_ok()
+        };
+        let relationship = |technology: String| {
+            let user = Person::builder()
+                .name("User".into())
+                .description("A user".into())
+                .build()
+                .unwrap();
+            Relationship::builder()
+                .source(user.clone())
+                .target(user)
+                .description("Talks to".into())
+                .technology(technology)
+                .build()
+                .is_ok()
+        };
+
+        let at_limit = "x".repeat(MAX_TECHNOLOGY_LENGTH);
+        let over_limit = "x".repeat(MAX_TECHNOLOGY_LENGTH + 1);
+        for accepts in [
+            &person as &dyn Fn(String) -> bool,
+            &container,
+            &component,
+            &relationship,
+        ] {
+            assert!(accepts(at_limit.clone()));
+            assert!(!accepts(over_limit.clone()));
+        }
+    }
+}
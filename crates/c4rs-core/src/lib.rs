@@ -10,6 +10,11 @@ pub use c4::component::{Component, ComponentError};
 pub use c4::container::{Container, ContainerError};
 pub use c4::context::{Person, PersonError, SoftwareSystem, SoftwareSystemError};
 pub use c4::element::{
-    CodeType, ContainerType, Element, ElementId, ElementType, InteractionStyle, Location,
+    CodeType, ComponentVisibility, ContainerType, Element, ElementId, ElementType,
+    InteractionStyle, Location,
+};
+pub use c4::model::{ModelChange, ModelDiff, ModelRelationship, WorkspaceModel, diff};
+pub use c4::model_builder::{ModelBuilder, ModelBuilderError};
+pub use c4::relationship::{
+    Relationship, RelationshipError, RelationshipTechnology, create_relationship,
 };
-pub use c4::relationship::{Relationship, RelationshipError, create_relationship};
@@ -25,11 +25,82 @@ pub enum DslError {
     #[error("duplicate identifier: {0}")]
     DuplicateIdentifier(String),
 
+    #[error("duplicate explicit identifier: {id}")]
+    DuplicateExplicitIdentifier { id: String },
+
     #[error("unsupported element type: {0}")]
     UnsupportedElementType(String),
 
+    #[error("code element {name} is not declared in the DSL and cannot be related")]
+    CodeElementRelationship { name: String },
+
+    #[error("relationship endpoint name is ambiguous: {name}")]
+    AmbiguousRelationshipEndpoint { name: String },
+
+    #[error("hierarchical identifier used in flat identifier mode: {id}")]
+    HierarchicalIdInFlatMode { id: String },
+
+    #[error("view {view} must be scoped to a {expected}, not a {actual}")]
+    InvalidViewScope {
+        view: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("deployment view {view} has no environment")]
+    MissingDeploymentEnvironment { view: String },
+
+    #[error("invalid value for style property {property}: {value}")]
+    InvalidStyleValue { property: String, value: String },
+
+    #[error("unknown view key: {key}")]
+    UnknownViewKey { key: String },
+
+    #[error("identifier directive must be a single token: {value:?}")]
+    InvalidIdentifierDirective { value: String },
+
+    #[error("tag contains a comma, which Structurizr treats as a separator: {tag:?}")]
+    InvalidTag { tag: String },
+
+    #[error("model has no persons or software systems")]
+    EmptyModel,
+
+    #[error("invalid workspace JSON: {reason}")]
+    InvalidWorkspaceJson { reason: String },
+
     #[error("template error: {0}")]
     TemplateError(String),
+
+    /// A rendering failure while writing `section` (`model`, `views`, or
+    /// `styles`), after `line` lines of output had been written.
+    #[error("error in {section} section after line {line}: {source}")]
+    InSection {
+        section: String,
+        line: usize,
+        #[source]
+        source: Box<DslError>,
+    },
+}
+
+/// Non-fatal diagnostics collected during serialization. The output is
+/// still valid DSL but probably not what was intended.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum DslWarning {
+    #[error("view {view} is scoped to {identifier}, which is not an element in the model")]
+    ViewScopeNotFound { view: String, identifier: String },
+
+    #[error("component {component} shares no technology with its container {container}")]
+    TechnologyMismatch {
+        container: String,
+        component: String,
+    },
+
+    #[error("identifier {id} is {length} characters long, which Structurizr may reject")]
+    LongIdentifier { id: String, length: usize },
+
+    #[error("relationship cycle: {}", .cycle.join(" -> "))]
+    RelationshipCycle { cycle: Vec<String> },
 }
 
 impl From<askama::Error> for DslError {
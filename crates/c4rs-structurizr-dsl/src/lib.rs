@@ -8,14 +8,20 @@ pub mod styles_serializer;
 pub mod templates;
 pub mod traits;
 pub mod views_serializer;
+#[cfg(feature = "json")]
+mod workspace_json;
 pub mod workspace_serializer;
 pub mod writer;
 
-pub use error::DslError;
+pub use error::{DslError, DslWarning};
 pub use identifier_generator::IdentifierGenerator;
 pub use structurizr_dsl::DslSerializer;
-pub use styles::{ElementStyle, RelationshipStyle};
+pub use styles::{BorderStyle, ElementStyle, RelationshipStyle, Router, Shape};
 pub use styles_serializer::StylesSerializer;
 pub use traits::escape_dsl_string;
-pub use views_serializer::{ViewConfiguration, ViewType, ViewsSerializer};
-pub use workspace_serializer::WorkspaceSerializer;
+pub use views_serializer::{StylesPosition, ViewConfiguration, ViewType, ViewsSerializer};
+pub use workspace_serializer::{
+    DEFAULT_MAX_IDENTIFIER_LENGTH, IdentifierHeader, IdentifierMode, STABLE_FORMAT_VERSION,
+    WorkspaceSerializer,
+};
+pub use writer::{format_dsl, minify_dsl, validate_dsl_syntax};
@@ -1,8 +1,8 @@
-use crate::error::DslError;
+use crate::error::{DslError, DslWarning};
 use crate::styles::{ElementStyle, RelationshipStyle};
-use crate::views_serializer::ViewConfiguration;
-use crate::workspace_serializer::WorkspaceSerializer;
-use c4rs_core::c4::{Element, Person, SoftwareSystem};
+use crate::views_serializer::{StylesPosition, ViewConfiguration};
+use crate::workspace_serializer::{IdentifierHeader, IdentifierMode, WorkspaceSerializer};
+use c4rs_core::c4::{Element, ElementType, Person, Relationship, SoftwareSystem};
 
 #[derive(Debug, Default)]
 pub struct DslSerializer {
@@ -16,16 +16,166 @@ impl DslSerializer {
         }
     }
 
-    pub fn with_name(mut self, name: &str) -> Self {
+    /// Pre-allocates room for the given numbers of software systems and
+    /// relationships. See [`WorkspaceSerializer::with_capacity_hints`].
+    pub fn with_capacity_hints(systems: usize, relationships: usize) -> Self {
+        Self {
+            inner: WorkspaceSerializer::with_capacity_hints(systems, relationships),
+        }
+    }
+
+    /// Loads a Structurizr `workspace.json` document so it can be re-emitted
+    /// as DSL. See [`WorkspaceSerializer::from_workspace_json`].
+    #[cfg(feature = "json")]
+    pub fn from_workspace_json(json: &str) -> Result<Self, DslError> {
+        Ok(Self {
+            inner: WorkspaceSerializer::from_workspace_json(json)?,
+        })
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
         self.inner = self.inner.name(name);
         self
     }
 
-    pub fn with_description(mut self, description: &str) -> Self {
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
         self.inner = self.inner.description(description);
         self
     }
 
+    /// Targets a specific Structurizr workspace when the generated DSL is
+    /// pushed to Structurizr cloud or on-premises.
+    pub fn with_workspace_id(mut self, id: i64) -> Self {
+        self.inner = self.inner.workspace_id(id);
+        self
+    }
+
+    /// Extends the base workspace at `path` (a file or URL) instead of
+    /// defining a standalone workspace.
+    pub fn with_extends(mut self, path: &str) -> Self {
+        self.inner = self.inner.extends(path);
+        self
+    }
+
+    /// Adds a workspace property. Properties are emitted sorted by key.
+    pub fn with_property(mut self, key: &str, value: &str) -> Self {
+        self.inner = self.inner.add_property(key, value);
+        self
+    }
+
+    /// Adds a boolean workspace property. See
+    /// [`WorkspaceSerializer::add_property_bool`].
+    pub fn with_property_bool(mut self, key: &str, value: bool) -> Self {
+        self.inner = self.inner.add_property_bool(key, value);
+        self
+    }
+
+    /// Adds a numeric workspace property. See
+    /// [`WorkspaceSerializer::add_property_number`].
+    pub fn with_property_number(mut self, key: &str, value: f64) -> Self {
+        self.inner = self.inner.add_property_number(key, value);
+        self
+    }
+
+    /// Sets the `!identifiers` strategy. Defaults to hierarchical.
+    pub fn with_identifier_mode(mut self, mode: IdentifierMode) -> Self {
+        self.inner = self.inner.identifier_mode(mode);
+        self
+    }
+
+    /// Controls when `!identifiers` is written. Defaults to always;
+    /// [`IdentifierHeader::Auto`] omits it for models without containers.
+    pub fn with_identifier_header(mut self, header: IdentifierHeader) -> Self {
+        self.inner = self.inner.identifier_header(header);
+        self
+    }
+
+    /// Writes a raw `!identifiers` value, bypassing [`IdentifierMode`] for
+    /// strategies added to Structurizr later. Serialization fails unless the
+    /// value is a single token.
+    pub fn with_identifier_directive(mut self, value: &str) -> Self {
+        self.inner = self.inner.identifier_directive(value);
+        self
+    }
+
+    /// Fills in empty relationship descriptions with a default based on the
+    /// endpoint types, such as `Uses` for relationships from a person.
+    pub fn with_inferred_descriptions(mut self, inferred: bool) -> Self {
+        self.inner = self.inner.inferred_descriptions(inferred);
+        self
+    }
+
+    /// Writes relationships inside their source element's block using
+    /// `this -> target` rather than in one list after the elements.
+    pub fn with_inline_relationships(mut self, inline: bool) -> Self {
+        self.inner = self.inner.inline_relationships(inline);
+        self
+    }
+
+    /// Fails serialization with [`DslError::EmptyModel`] when nothing was
+    /// added, so CI catches accidentally empty generation. Defaults to off.
+    pub fn with_require_nonempty(mut self, require: bool) -> Self {
+        self.inner = self.inner.require_nonempty(require);
+        self
+    }
+
+    /// Sets the identifier length above which a
+    /// [`DslWarning::LongIdentifier`] is reported. Defaults to
+    /// [`crate::DEFAULT_MAX_IDENTIFIER_LENGTH`].
+    pub fn with_max_identifier_length(mut self, length: usize) -> Self {
+        self.inner = self.inner.max_identifier_length(length);
+        self
+    }
+
+    /// Places the `styles` block before or after the view definitions.
+    /// Defaults to after.
+    pub fn with_styles_position(mut self, position: StylesPosition) -> Self {
+        self.inner = self.inner.styles_position(position);
+        self
+    }
+
+    /// Opens the view with the given key first when the workspace is
+    /// loaded. Serialization fails if no added view has that key.
+    pub fn with_default_view(mut self, key: &str) -> Self {
+        self.inner = self.inner.default_view(key);
+        self
+    }
+
+    /// Emits `include *` for views without include expressions so they
+    /// are not rendered empty. Defaults to enabled.
+    pub fn with_auto_include(mut self, auto_include: bool) -> Self {
+        self.inner = self.inner.auto_include(auto_include);
+        self
+    }
+
+    /// Groups internal persons and software systems inside a named
+    /// enterprise boundary, leaving external elements outside of it.
+    pub fn with_enterprise_boundary(mut self, name: &str) -> Self {
+        self.inner = self.inner.enterprise_boundary(name);
+        self
+    }
+
+    /// Omits the blank separator lines between sections when false, keeping
+    /// indentation. Defaults to true.
+    pub fn with_blank_lines(mut self, blank_lines: bool) -> Self {
+        self.inner = self.inner.blank_lines(blank_lines);
+        self
+    }
+
+    /// Emits the workspace with minimal whitespace, e.g. for logs or
+    /// snapshots. Defaults to pretty output.
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.inner = self.inner.compact(compact);
+        self
+    }
+
+    /// Adds a `#` comment at the top of the generated file. Comments on
+    /// individual elements are attached with `with_comment` on the element.
+    pub fn add_comment(mut self, comment: &str) -> Self {
+        self.inner = self.inner.add_comment(comment);
+        self
+    }
+
     pub fn add_person(mut self, person: &Person) -> Self {
         self.inner = self.inner.add_person(person);
         self
@@ -36,11 +186,32 @@ impl DslSerializer {
         self
     }
 
+    /// Adds a person by value, avoiding the clone made by
+    /// [`add_person`](Self::add_person) for models built once.
+    pub fn add_person_owned(mut self, person: Person) -> Self {
+        self.inner = self.inner.add_person_owned(person);
+        self
+    }
+
+    /// Adds a software system by value, avoiding the clone made by
+    /// [`add_software_system`](Self::add_software_system).
+    pub fn add_software_system_owned(mut self, system: SoftwareSystem) -> Self {
+        self.inner = self.inner.add_software_system_owned(system);
+        self
+    }
+
     pub fn add_view(mut self, view: ViewConfiguration) -> Self {
         self.inner = self.inner.add_view(view);
         self
     }
 
+    /// Applies an external theme, e.g. a shared corporate one, by URL.
+    /// Several themes are written as one `themes` line.
+    pub fn add_theme(mut self, url: &str) -> Self {
+        self.inner = self.inner.add_theme(url);
+        self
+    }
+
     pub fn add_element_style(mut self, style: ElementStyle) -> Self {
         self.inner = self.inner.add_element_style(style);
         self
@@ -64,9 +235,146 @@ impl DslSerializer {
         self
     }
 
+    /// Adds a core relationship including its url and tags. See
+    /// [`WorkspaceSerializer::add_typed_relationship`].
+    pub fn add_typed_relationship<S: Element, T: Element>(
+        mut self,
+        relationship: &Relationship<S, T>,
+    ) -> Self {
+        self.inner = self.inner.add_typed_relationship(relationship);
+        self
+    }
+
+    /// Relates two elements already added to the model by their resolved
+    /// DSL paths, failing at serialize time if either is missing.
+    pub fn relate(mut self, from: &impl Element, to: &impl Element, description: &str) -> Self {
+        self.inner = self.inner.relate(from, to, description);
+        self
+    }
+
+    pub fn add_relationship_by_name(
+        mut self,
+        source_name: &str,
+        target_name: &str,
+        description: &str,
+        technology: Option<&str>,
+    ) -> Self {
+        self.inner =
+            self.inner
+                .add_relationship_by_name(source_name, target_name, description, technology);
+        self
+    }
+
+    pub fn add_relationship_by_identifier(
+        mut self,
+        source_identifier: &str,
+        target_identifier: &str,
+        description: &str,
+        technology: Option<&str>,
+    ) -> Self {
+        self.inner = self.inner.add_relationship_by_identifier(
+            source_identifier,
+            target_identifier,
+            description,
+            technology,
+        );
+        self
+    }
+
+    /// Counts every element in the model, including nested ones, without
+    /// serializing.
+    pub fn count_elements(&self) -> usize {
+        self.inner.count_elements()
+    }
+
+    pub fn count_relationships(&self) -> usize {
+        self.inner.count_relationships()
+    }
+
+    /// Estimates the number of output lines, used to pre-allocate the
+    /// writer during serialization.
+    pub fn estimated_capacity(&self) -> usize {
+        self.inner.estimated_capacity()
+    }
+
+    /// Serializes the workspace and returns the output together with any
+    /// non-fatal diagnostics, such as views scoped to unknown elements.
+    pub fn serialize_with_warnings(self) -> Result<(String, Vec<DslWarning>), DslError> {
+        self.inner.serialize_with_warnings()
+    }
+
+    /// Collects the non-fatal diagnostics without consuming the serializer.
+    /// See [`WorkspaceSerializer::warnings`].
+    pub fn warnings(&self) -> Result<Vec<DslWarning>, DslError> {
+        self.inner.warnings()
+    }
+
+    /// Reports relationship cycles as lists of DSL paths. See
+    /// [`WorkspaceSerializer::detect_cycles`].
+    pub fn detect_cycles(&self) -> Vec<Vec<String>> {
+        self.inner.detect_cycles()
+    }
+
+    /// Resolves element identifiers ahead of serialization. See
+    /// [`WorkspaceSerializer::build_paths`].
+    pub fn build_paths(&mut self) -> Result<(), DslError> {
+        self.inner.build_paths()
+    }
+
+    /// Lists `(type, name, DSL identifier)` for every element once
+    /// [`build_paths`](Self::build_paths) has run, e.g. to generate
+    /// documentation tables.
+    pub fn iter_resolved(&self) -> impl Iterator<Item = (ElementType, String, String)> {
+        self.inner.iter_resolved()
+    }
+
+    /// Checks for authoring mistakes such as unresolvable relationship
+    /// endpoints or a component view scoped to a software system.
+    pub fn validate(&self) -> Result<(), DslError> {
+        self.inner.validate()
+    }
+
     pub fn serialize(self) -> Result<String, DslError> {
         self.inner.serialize()
     }
+
+    /// Serializes the full model but emits only the views whose keys are
+    /// listed. A view's key is its explicit key, or otherwise its title with
+    /// spaces replaced by underscores.
+    pub fn serialize_views(self, keys: &[&str]) -> Result<String, DslError> {
+        self.inner.serialize_views(keys)
+    }
+
+    /// Serializes the full model but emits only the views carrying `tag`,
+    /// e.g. to publish the "public" subset of a larger set of views.
+    pub fn serialize_views_by_tag(self, tag: &str) -> Result<String, DslError> {
+        self.inner.serialize_views_by_tag(tag)
+    }
+
+    /// Returns a stable hash of the serialized output, e.g. for CI to detect
+    /// whether generated DSL changed. See [`WorkspaceSerializer::content_hash`].
+    pub fn content_hash(self) -> Result<u64, DslError> {
+        self.inner.content_hash()
+    }
+
+    /// Splits the model into one serializer per software system, e.g. to
+    /// write a folder of focused DSL files. See
+    /// [`WorkspaceSerializer::split_by_system`].
+    pub fn split_by_system(&self) -> Vec<(String, DslSerializer)> {
+        self.inner
+            .split_by_system()
+            .into_iter()
+            .map(|(name, inner)| (name, DslSerializer { inner }))
+            .collect()
+    }
+
+    /// Serializes with formatting that is guaranteed not to change within a
+    /// major version (see [`crate::STABLE_FORMAT_VERSION`]), making the output safe
+    /// to check into version control. [`serialize`](Self::serialize) may
+    /// evolve its formatting between minor releases.
+    pub fn serialize_stable(self) -> Result<String, DslError> {
+        self.inner.serialize_stable()
+    }
 }
 
 #[cfg(test)]
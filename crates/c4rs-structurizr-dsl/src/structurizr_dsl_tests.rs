@@ -1,6 +1,10 @@
 use super::*;
+use crate::error::DslWarning;
 use crate::views_serializer::ViewType;
-use c4rs_core::c4::{Container, ContainerType, Person, SoftwareSystem};
+use crate::workspace_serializer::IdentifierHeader;
+use c4rs_core::c4::{
+    Component, Container, ContainerType, ElementType, Location, Person, SoftwareSystem,
+};
 
 #[test]
 fn test_serialize_empty_model() {
@@ -10,6 +14,54 @@ fn test_serialize_empty_model() {
     assert!(result.contains("model {"));
 }
 
+#[test]
+fn test_require_nonempty() {
+    let result = DslSerializer::new().with_require_nonempty(true).serialize();
+    assert!(matches!(result, Err(DslError::EmptyModel)));
+
+    let person = Person::builder()
+        .name("User".into())
+        .description("A user".into())
+        .build()
+        .unwrap();
+    assert!(
+        DslSerializer::new()
+            .with_require_nonempty(true)
+            .add_person(&person)
+            .serialize()
+            .is_ok()
+    );
+    assert!(DslSerializer::new().serialize().is_ok());
+}
+
+#[test]
+fn test_with_capacity_hints() {
+    let systems: Vec<SoftwareSystem> = (0..5000)
+        .map(|i| {
+            SoftwareSystem::builder()
+                .name(format!("System {}", i))
+                .description("Generated".into())
+                .build()
+                .unwrap()
+                .with_identifier(&format!("s{}", i))
+        })
+        .collect();
+
+    let mut serializer = DslSerializer::with_capacity_hints(systems.len(), systems.len() - 1);
+    for system in &systems {
+        serializer = serializer.add_software_system(system);
+    }
+    for pair in systems.windows(2) {
+        serializer = serializer.add_relationship(&pair[0], &pair[1], "Calls", None);
+    }
+    let result = serializer.serialize().unwrap();
+
+    assert_eq!(result.matches("= softwareSystem").count(), 5000);
+    assert!(result.contains(r#"s4999 = softwareSystem "System 4999" "Generated""#));
+    assert!(result.contains(r#"s4998 -> s4999 "Calls""#));
+    assert_eq!(result.matches(" -> ").count(), 4999);
+}
+
 #[test]
 fn test_serialize_single_person() {
     let person: Person = Person::builder()
@@ -24,6 +76,80 @@ fn test_serialize_single_person() {
     assert!(result.contains(r#"u = person "User" "A system user""#));
 }
 
+#[test]
+fn test_serialize_description_less_elements() {
+    let system = SoftwareSystem::builder()
+        .name("Billing".into())
+        .without_description()
+        .add_container(
+            &Container::builder()
+                .name("Ledger".into())
+                .without_description()
+                .container_type(ContainerType::Database)
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+    assert_eq!(system.description(), "");
+
+    let result = DslSerializer::new()
+        .add_software_system(&system)
+        .serialize()
+        .unwrap();
+    assert!(result.contains(r#"b = softwareSystem "Billing" {"#));
+    assert!(result.contains(r#"l = container "Ledger" {}"#));
+}
+
+#[test]
+fn test_owned_elements_match_borrowed() {
+    let person = Person::builder()
+        .name("User".into())
+        .description("A user".into())
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("Shop".into())
+        .description("Online shop".into())
+        .add_container(
+            &Container::builder()
+                .name("API".into())
+                .description("Backend".into())
+                .container_type(ContainerType::Api)
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    let borrowed = DslSerializer::new()
+        .add_person(&person)
+        .add_software_system(&system)
+        .add_relationship(&person, &system, "Uses", None)
+        .serialize()
+        .unwrap();
+    // Relationships only store ids, so they can be added before the
+    // elements are moved in.
+    let owned = DslSerializer::new()
+        .add_relationship(&person, &system, "Uses", None)
+        .add_person_owned(person)
+        .add_software_system_owned(system)
+        .serialize()
+        .unwrap();
+    assert_eq!(owned, borrowed);
+}
+
+#[test]
+fn test_with_name_accepts_owned_string() {
+    let name = format!("{} Workspace", "Owned");
+    let result = DslSerializer::new()
+        .with_name(name)
+        .with_description(String::from("Built from owned strings"))
+        .serialize()
+        .unwrap();
+    assert!(result.starts_with(r#"workspace "Owned Workspace" "Built from owned strings""#));
+}
+
 #[test]
 fn test_serialize_full_model() {
     let person: Person = Person::builder()
@@ -252,6 +378,44 @@ fn test_nested_container_serialization() {
     assert!(result.contains("wa = container \"Web App\" \"Frontend\""));
 }
 
+#[test]
+fn test_iter_resolved_yields_nested_paths() {
+    let system: SoftwareSystem = SoftwareSystem::builder()
+        .name("BankApp".into())
+        .description("Banking App".into())
+        .add_container(
+            &Container::builder()
+                .name("Web App".into())
+                .description("Frontend".into())
+                .container_type(ContainerType::WebApplication)
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    let mut serializer = DslSerializer::new().add_software_system(&system);
+    assert_eq!(serializer.iter_resolved().count(), 0);
+
+    serializer.build_paths().unwrap();
+    let resolved: Vec<_> = serializer.iter_resolved().collect();
+    assert_eq!(
+        resolved,
+        vec![
+            (
+                ElementType::SoftwareSystem,
+                "BankApp".to_string(),
+                "b".to_string()
+            ),
+            (
+                ElementType::Container,
+                "Web App".to_string(),
+                "b.wa".to_string()
+            ),
+        ]
+    );
+}
+
 #[test]
 fn test_circular_relationships() {
     let person: Person = Person::builder()
@@ -466,3 +630,801 @@ fn test_golden_file_complete_workspace() {
 
     assert_eq!(result, expected);
 }
+
+#[test]
+fn test_relationship_by_name() {
+    let web_app = Container::builder()
+        .name("Web App".into())
+        .description("Frontend".into())
+        .container_type(ContainerType::WebApplication)
+        .build()
+        .unwrap();
+    let person = Person::builder()
+        .name("User".into())
+        .description("A user".into())
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .add_container(&web_app)
+        .build()
+        .unwrap();
+
+    let result = DslSerializer::new()
+        .add_person(&person)
+        .add_software_system(&system)
+        .add_relationship_by_name("User", "Web App", "Uses", Some("HTTPS"))
+        .serialize()
+        .unwrap();
+
+    assert!(result.contains(r#"u -> a.wa "Uses" "HTTPS""#));
+}
+
+#[test]
+fn test_relationship_by_name_ambiguous() {
+    let first = Person::builder()
+        .name("User".into())
+        .description("A user".into())
+        .build()
+        .unwrap();
+    let second = Person::builder()
+        .name("User".into())
+        .description("Another user".into())
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .build()
+        .unwrap();
+
+    let result = DslSerializer::new()
+        .add_person(&first)
+        .add_person(&second)
+        .add_software_system(&system)
+        .add_relationship_by_name("User", "API", "Uses", None)
+        .serialize();
+
+    assert!(matches!(
+        result,
+        Err(DslError::AmbiguousRelationshipEndpoint { name }) if name == "User"
+    ));
+}
+
+#[test]
+fn test_compact_output() {
+    let person = Person::builder()
+        .name("User".into())
+        .description("A user".into())
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .add_container(
+            &Container::builder()
+                .name("Web App".into())
+                .description("Frontend".into())
+                .container_type(ContainerType::WebApplication)
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+    let view = || {
+        ViewConfiguration::builder()
+            .view_type(ViewType::SystemContext)
+            .element_identifier("a".to_string())
+            .title("Context".to_string())
+            .include_elements(vec!["*".to_string()])
+            .build()
+    };
+    let style = || {
+        ElementStyle::builder()
+            .identifier("Person".into())
+            .shape("person".into())
+            .build()
+    };
+    let build = |compact: bool| {
+        DslSerializer::new()
+            .with_compact(compact)
+            .add_person(&person)
+            .add_software_system(&system)
+            .add_relationship(&person, &system, "Uses", None)
+            .add_view(view())
+            .add_element_style(style())
+            .serialize()
+            .unwrap()
+    };
+
+    let pretty = build(false);
+    let compact = build(true);
+
+    assert!(!compact.contains("    "));
+    assert!(!compact.contains("\n\n"));
+    let normalized: Vec<&str> = pretty
+        .lines()
+        .map(str::trim_start)
+        .filter(|l| !l.is_empty())
+        .collect();
+    assert_eq!(compact.lines().collect::<Vec<_>>(), normalized);
+    let count_elements = |dsl: &str| {
+        [" = person ", " = softwareSystem ", " = container "]
+            .iter()
+            .map(|keyword| dsl.matches(keyword).count())
+            .sum::<usize>()
+    };
+    assert_eq!(count_elements(&compact), 3);
+    assert_eq!(count_elements(&compact), count_elements(&pretty));
+}
+
+#[test]
+fn test_without_blank_lines() {
+    let person = Person::builder()
+        .name("User".into())
+        .description("A user".into())
+        .build()
+        .unwrap();
+    let build = |blank_lines: bool| {
+        DslSerializer::new()
+            .with_blank_lines(blank_lines)
+            .add_person(&person)
+            .add_view(
+                ViewConfiguration::builder()
+                    .view_type(ViewType::SystemLandscape)
+                    .element_identifier("*".to_string())
+                    .title("Landscape".to_string())
+                    .build(),
+            )
+            .add_element_style(
+                ElementStyle::builder()
+                    .identifier("Person".into())
+                    .shape("person".into())
+                    .build(),
+            )
+            .serialize()
+            .unwrap()
+    };
+
+    let spaced = build(true);
+    let tight = build(false);
+
+    assert!(spaced.contains("\n\n"));
+    assert!(!tight.lines().any(|line| line.trim().is_empty()));
+    assert!(tight.contains("\n    model {"));
+    assert_eq!(tight.matches('{').count(), tight.matches('}').count());
+    let non_blank: Vec<&str> = spaced.lines().filter(|l| !l.trim().is_empty()).collect();
+    assert_eq!(tight.lines().collect::<Vec<_>>(), non_blank);
+}
+
+#[test]
+fn test_enterprise_boundary() {
+    let internal = SoftwareSystem::builder()
+        .name("Internal".into())
+        .description("Owned by us".into())
+        .build()
+        .unwrap();
+    let external = SoftwareSystem::builder()
+        .name("External".into())
+        .description("Owned by a partner".into())
+        .location(Location::External)
+        .build()
+        .unwrap();
+
+    let result = DslSerializer::new()
+        .with_enterprise_boundary("Big Bank plc")
+        .add_software_system(&external)
+        .add_software_system(&internal)
+        .serialize()
+        .unwrap();
+
+    assert!(result.contains(
+        r#"        group "Big Bank plc" {
+            i = softwareSystem "Internal" "Owned by us"
+        }
+        e = softwareSystem "External" "Owned by a partner""#
+    ));
+}
+
+#[test]
+fn test_enterprise_boundary_places_persons_by_location() {
+    let clerk = Person::builder()
+        .name("Clerk".into())
+        .description("Bank staff".into())
+        .build()
+        .unwrap();
+    let customer = Person::builder()
+        .name("Customer".into())
+        .description("Bank customer".into())
+        .location(Location::External)
+        .build()
+        .unwrap();
+
+    let result = DslSerializer::new()
+        .with_enterprise_boundary("Big Bank plc")
+        .add_person(&customer)
+        .add_person(&clerk)
+        .serialize()
+        .unwrap();
+
+    assert!(result.contains(
+        r#"        group "Big Bank plc" {
+            c = person "Clerk" "Bank staff"
+        }
+        c1 = person "Customer" "Bank customer" {
+            tags "External"
+        }"#
+    ));
+}
+
+#[test]
+fn test_serialize_views_subset() {
+    let system = SoftwareSystem::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .build()
+        .unwrap();
+    let view = |view_type: ViewType, title: &str| {
+        ViewConfiguration::builder()
+            .view_type(view_type)
+            .element_identifier("a".to_string())
+            .title(title.to_string())
+            .include_elements(vec!["*".to_string()])
+            .build()
+    };
+    let serializer = || {
+        DslSerializer::new()
+            .add_software_system(&system)
+            .add_view(view(ViewType::SystemLandscape, "Landscape"))
+            .add_view(view(ViewType::SystemContext, "System Context"))
+            .add_view(view(ViewType::Container, "Containers"))
+    };
+
+    let result = serializer().serialize_views(&["System_Context"]).unwrap();
+
+    assert!(result.contains(r#"a = softwareSystem "API""#));
+    assert!(result.contains(r#"systemContext a "System_Context" {"#));
+    assert!(!result.contains("Landscape"));
+    assert!(!result.contains("Containers"));
+
+    let unknown = serializer().serialize_views(&["Missing"]);
+    assert!(matches!(
+        unknown,
+        Err(DslError::UnknownViewKey { key }) if key == "Missing"
+    ));
+}
+
+#[test]
+fn test_serialize_large_model() {
+    // Spell each index as space-separated letters so every generated
+    // identifier is unique, e.g. 27 -> "b b".
+    let name = |mut i: usize| {
+        let mut words = Vec::new();
+        loop {
+            words.push(char::from(b'a' + (i % 26) as u8).to_string());
+            i /= 26;
+            if i == 0 {
+                break;
+            }
+        }
+        words.join(" ")
+    };
+    let persons: Vec<Person> = (0..5000)
+        .map(|i| {
+            Person::builder()
+                .name(name(i))
+                .description("A user".into())
+                .build()
+                .unwrap()
+        })
+        .collect();
+
+    let serializer = persons
+        .iter()
+        .fold(DslSerializer::new(), |s, p| s.add_person(p))
+        .add_relationship(&persons[0], &persons[4999], "Knows", None);
+    let capacity = serializer.estimated_capacity();
+    let result = serializer.serialize().unwrap();
+
+    assert_eq!(result.matches(" = person ").count(), 5000);
+    assert!(result.contains(r#"hkh = person "h k h" "A user""#));
+    assert!(result.contains(r#"a -> hkh "Knows""#));
+    assert!(capacity >= result.lines().count());
+}
+
+#[test]
+fn test_warning_for_view_scope_not_found() {
+    let system = SoftwareSystem::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .build()
+        .unwrap();
+    let view = |identifier: &str, title: &str| {
+        ViewConfiguration::builder()
+            .view_type(ViewType::SystemContext)
+            .element_identifier(identifier.to_string())
+            .title(title.to_string())
+            .include_elements(vec!["*".to_string()])
+            .build()
+    };
+
+    let (dsl, warnings) = DslSerializer::new()
+        .add_software_system(&system)
+        .add_view(view("a", "Good"))
+        .add_view(view("missing", "Bad View"))
+        .serialize_with_warnings()
+        .unwrap();
+
+    assert!(dsl.contains(r#"systemContext missing "Bad_View" {"#));
+    assert_eq!(
+        warnings,
+        vec![DslWarning::ViewScopeNotFound {
+            view: "Bad_View".to_string(),
+            identifier: "missing".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_warnings_without_consuming() {
+    let component = Component::builder()
+        .name("Handler".into())
+        .description("Handles requests".into())
+        .technology("Go".into())
+        .build()
+        .unwrap();
+    let container = Container::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .container_type(ContainerType::Api)
+        .technology("Rust".into())
+        .add_component(&component)
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("Shop".into())
+        .description("Online shop".into())
+        .add_container(&container)
+        .build()
+        .unwrap();
+    let serializer = DslSerializer::new().add_software_system(&system).add_view(
+        ViewConfiguration::builder()
+            .view_type(ViewType::Container)
+            .element_identifier("missing".into())
+            .title("Containers".into())
+            .build(),
+    );
+
+    let warnings = serializer.warnings().unwrap();
+    assert_eq!(
+        warnings,
+        vec![
+            DslWarning::ViewScopeNotFound {
+                view: "Containers".into(),
+                identifier: "missing".into(),
+            },
+            DslWarning::TechnologyMismatch {
+                container: "API".into(),
+                component: "Handler".into(),
+            },
+        ]
+    );
+    let (_, serialized) = serializer.serialize_with_warnings().unwrap();
+    assert_eq!(serialized, warnings);
+}
+
+#[test]
+fn test_fluent_uses_relationship() {
+    let api = Container::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .container_type(ContainerType::Api)
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("Shop".into())
+        .description("Online shop".into())
+        .add_container(&api)
+        .build()
+        .unwrap();
+    let mut customer = Person::builder()
+        .name("Customer".into())
+        .description("Buys things".into())
+        .build()
+        .unwrap();
+    let relationship = customer.uses(&api, "Places orders with", Some("HTTP"));
+    assert_eq!(relationship.target(), api.id());
+    assert_eq!(customer.outgoing_relationships().len(), 1);
+
+    let dsl = DslSerializer::new()
+        .add_person(&customer)
+        .add_software_system(&system)
+        .serialize()
+        .unwrap();
+    assert!(
+        dsl.contains(r#"c -> s.a "Places orders with" "HTTP""#),
+        "{dsl}"
+    );
+}
+
+#[test]
+fn test_serialize_multi_line_workspace_description() {
+    let dsl = DslSerializer::new()
+        .with_name("Banking")
+        .with_description("Internet banking.\nMaintained by the \"core\" team.")
+        .serialize()
+        .unwrap();
+
+    let header = dsl.lines().next().unwrap();
+    assert_eq!(
+        header,
+        r#"workspace "Banking" "Internet banking.\nMaintained by the \"core\" team." {"#
+    );
+}
+
+#[test]
+fn test_serialize_escapes_interpolation_sequences() {
+    let person: Person = Person::builder()
+        .name("Deployer".into())
+        .description("Deploys build ${VERSION}".into())
+        .build()
+        .unwrap();
+
+    let dsl = DslSerializer::new()
+        .add_person(&person)
+        .serialize()
+        .unwrap();
+
+    assert!(dsl.contains(r#"d = person "Deployer" "Deploys build \${VERSION}""#));
+    assert!(!dsl.contains(r#" ${VERSION}"#));
+}
+
+#[test]
+fn test_serialize_workspace_id_property() {
+    let dsl = DslSerializer::new()
+        .with_name("Banking")
+        .with_workspace_id(12345)
+        .serialize()
+        .unwrap();
+
+    assert!(dsl.contains(
+        "    !identifiers hierarchical\n    properties {\n        \"structurizr.workspaceId\" \"12345\"\n    }\n\n    model {"
+    ));
+}
+
+#[test]
+fn test_serialize_extends_header() {
+    let dsl = DslSerializer::new()
+        .with_name("Payments")
+        .with_description("Payments team view")
+        .with_extends(r#"shared/"base".dsl"#)
+        .serialize()
+        .unwrap();
+
+    assert!(dsl.starts_with(
+        r#"workspace extends "shared/\"base\".dsl" "Payments" "Payments team view" {"#
+    ));
+}
+
+#[test]
+fn test_content_hash() {
+    let hash = |name: &str| {
+        let person = Person::builder()
+            .name(name.into())
+            .description("A user".into())
+            .build()
+            .unwrap();
+        DslSerializer::new()
+            .add_person(&person)
+            .content_hash()
+            .unwrap()
+    };
+
+    assert_eq!(hash("User"), hash("User"));
+    assert_ne!(hash("User"), hash("Admin"));
+    assert_eq!(
+        DslSerializer::new().content_hash().unwrap(),
+        DslSerializer::new().content_hash().unwrap()
+    );
+}
+
+#[test]
+fn test_workspace_properties_sorted_by_key() {
+    let dsl = DslSerializer::new()
+        .with_property("zone", "eu-west")
+        .with_property("owner", "platform")
+        .with_workspace_id(7)
+        .with_property("cost-centre", "42")
+        .serialize()
+        .unwrap();
+
+    assert!(dsl.contains(
+        "    properties {\n        \"cost-centre\" \"42\"\n        \"owner\" \"platform\"\n        \"structurizr.workspaceId\" \"7\"\n        \"zone\" \"eu-west\"\n    }\n"
+    ));
+}
+
+#[test]
+fn test_serialize_comments() {
+    let system = SoftwareSystem::builder()
+        .name("Payments".into())
+        .description("Moves money".into())
+        .build()
+        .unwrap()
+        .with_comment("Owned by the payments team");
+
+    let dsl = DslSerializer::new()
+        .add_comment("Generated file, do not edit")
+        .add_software_system(&system)
+        .serialize()
+        .unwrap();
+
+    assert!(dsl.starts_with("# Generated file, do not edit\nworkspace "));
+    assert!(dsl.contains(
+        "        # Owned by the payments team\n        p = softwareSystem \"Payments\" \"Moves money\"\n"
+    ));
+}
+
+#[test]
+fn test_identifier_header_auto() {
+    let person: Person = Person::builder()
+        .name("User".into())
+        .description("A system user".into())
+        .build()
+        .unwrap();
+    let api = Container::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .container_type(ContainerType::Api)
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("Shop".into())
+        .description("Online shop".into())
+        .add_container(&api)
+        .build()
+        .unwrap();
+
+    let flat = DslSerializer::new()
+        .with_identifier_header(IdentifierHeader::Auto)
+        .add_person(&person)
+        .serialize()
+        .unwrap();
+    assert!(!flat.contains("!identifiers"));
+    assert!(flat.contains("{\n    model {"));
+
+    let nested = DslSerializer::new()
+        .with_identifier_header(IdentifierHeader::Auto)
+        .add_person(&person)
+        .add_software_system(&system)
+        .serialize()
+        .unwrap();
+    assert!(nested.contains("!identifiers hierarchical"));
+
+    let always = DslSerializer::new()
+        .add_person(&person)
+        .serialize()
+        .unwrap();
+    assert!(always.contains("!identifiers hierarchical"));
+}
+
+#[test]
+fn test_identifier_directive_passthrough() {
+    let custom = DslSerializer::new()
+        .with_identifier_directive("semantic")
+        .serialize()
+        .unwrap();
+    assert!(custom.contains("    !identifiers semantic\n"));
+    assert!(!custom.contains("!identifiers hierarchical"));
+
+    for value in ["", "two tokens"] {
+        let result = DslSerializer::new()
+            .with_identifier_directive(value)
+            .serialize();
+        assert!(matches!(
+            result,
+            Err(DslError::InvalidIdentifierDirective { .. })
+        ));
+    }
+}
+
+#[test]
+fn test_inferred_relationship_descriptions() {
+    let person = Person::builder()
+        .name("User".into())
+        .description("A user".into())
+        .build()
+        .unwrap();
+    let web = Container::builder()
+        .name("Web".into())
+        .description("Frontend".into())
+        .container_type(ContainerType::WebApplication)
+        .build()
+        .unwrap();
+    let db = Container::builder()
+        .name("Database".into())
+        .description("Orders".into())
+        .container_type(ContainerType::Database)
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("Shop".into())
+        .description("Online shop".into())
+        .add_container(&web)
+        .add_container(&db)
+        .build()
+        .unwrap();
+    let serializer = |inferred: bool| {
+        DslSerializer::new()
+            .with_inferred_descriptions(inferred)
+            .add_person(&person)
+            .add_software_system(&system)
+            .add_relationship(&person, &web, "", None)
+            .add_relationship(&web, &db, "", None)
+            .add_relationship(&web, &system, "Calls", None)
+            .serialize()
+            .unwrap()
+    };
+
+    let result = serializer(true);
+    assert!(result.contains(r#"u -> s.w "Uses""#));
+    assert!(result.contains(r#"s.w -> s.d "Reads from and writes to""#));
+    assert!(result.contains(r#"s.w -> s "Calls""#));
+    assert!(serializer(false).contains(r#"u -> s.w """#));
+}
+
+#[test]
+fn test_inline_relationships() {
+    let person = Person::builder()
+        .name("User".into())
+        .description("A user".into())
+        .build()
+        .unwrap();
+    let api = Container::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .container_type(ContainerType::Api)
+        .build()
+        .unwrap();
+    let shop = SoftwareSystem::builder()
+        .name("Shop".into())
+        .description("Online shop".into())
+        .add_container(&api)
+        .build()
+        .unwrap();
+    let payments = SoftwareSystem::builder()
+        .name("Payments".into())
+        .description("Card payments".into())
+        .build()
+        .unwrap();
+
+    let result = DslSerializer::new()
+        .with_inline_relationships(true)
+        .add_person(&person)
+        .add_software_system(&shop)
+        .add_software_system(&payments)
+        .add_relationship(&person, &shop, "Browses", None)
+        .add_relationship(&shop, &payments, "Charges cards", Some("HTTPS"))
+        .add_relationship(&api, &payments, "Refunds", None)
+        .add_relationship_by_identifier("p", "u", "Notifies", None)
+        .serialize()
+        .unwrap();
+
+    assert!(result.contains(
+        r#"        u = person "User" "A user" {
+            this -> s "Browses"
+        }
+        s = softwareSystem "Shop" "Online shop" {
+            a = container "API" "Backend" {
+                this -> p "Refunds"
+            }
+            this -> p "Charges cards" "HTTPS"
+        }
+        p = softwareSystem "Payments" "Card payments"
+        p -> u "Notifies"
+"#
+    ));
+    assert!(!result.contains("u -> s"));
+}
+
+#[test]
+fn test_split_by_system() {
+    let person = Person::builder()
+        .name("User".into())
+        .description("A user".into())
+        .build()
+        .unwrap();
+    let api = Container::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .container_type(ContainerType::Api)
+        .build()
+        .unwrap();
+    let shop = SoftwareSystem::builder()
+        .name("Shop".into())
+        .description("Online shop".into())
+        .add_container(&api)
+        .build()
+        .unwrap();
+    let ledger = Container::builder()
+        .name("Ledger".into())
+        .description("Balances".into())
+        .container_type(ContainerType::Database)
+        .build()
+        .unwrap();
+    let payments = SoftwareSystem::builder()
+        .name("Payments".into())
+        .description("Card payments".into())
+        .add_container(&ledger)
+        .build()
+        .unwrap();
+    let serializer = DslSerializer::new()
+        .with_name("Company")
+        .add_person(&person)
+        .add_software_system(&shop)
+        .add_software_system(&payments)
+        .add_relationship(&person, &api, "Browses", None)
+        .add_relationship(&api, &payments, "Charges cards", None)
+        .add_relationship(&api, &ledger, "Peeks at", None);
+
+    let splits: Vec<(String, String)> = serializer
+        .split_by_system()
+        .into_iter()
+        .map(|(name, split)| (name, split.serialize().unwrap()))
+        .collect();
+    assert_eq!(splits.len(), 2);
+
+    let (name, dsl) = &splits[0];
+    assert_eq!(name, "Shop");
+    assert!(dsl.starts_with(r#"workspace "Shop" "Online shop" {"#));
+    assert!(dsl.contains(r#"u = person "User" "A user""#));
+    assert!(dsl.contains(r#"a = container "API" "Backend" {}"#));
+    assert!(dsl.contains(r#"p = softwareSystem "Payments" "Card payments""#));
+    assert!(!dsl.contains("Ledger"));
+    assert!(dsl.contains(r#"u -> s.a "Browses""#));
+    assert!(dsl.contains(r#"s.a -> p "Charges cards""#));
+    assert!(!dsl.contains("Peeks at"));
+
+    let (name, dsl) = &splits[1];
+    assert_eq!(name, "Payments");
+    assert!(dsl.contains(r#"l = container "Ledger" "Balances" {}"#));
+    assert!(dsl.contains(r#"u = person "User" "A user""#));
+    assert!(!dsl.contains("softwareSystem \"Shop\""));
+    assert!(!dsl.contains("->"));
+}
+
+#[test]
+fn test_add_theme() {
+    let result = DslSerializer::new()
+        .add_theme("https://example.com/theme.json")
+        .serialize()
+        .unwrap();
+    assert!(
+        result.contains("    views {\n        theme \"https://example.com/theme.json\"\n    }")
+    );
+}
+
+#[test]
+fn test_serialize_views_by_tag() {
+    let view = |title: &str, tags: &[&str]| {
+        ViewConfiguration::builder()
+            .view_type(ViewType::SystemLandscape)
+            .element_identifier(String::new())
+            .title(title.to_string())
+            .tags(tags.iter().map(|t| t.to_string()).collect())
+            .build()
+    };
+
+    let dsl = DslSerializer::new()
+        .add_view(view("Overview", &["public"]))
+        .add_view(view("Internals", &["internal"]))
+        .add_view(view("Landscape", &["public", "summary"]))
+        .serialize_views_by_tag("public")
+        .unwrap();
+
+    assert!(dsl.contains("systemLandscape \"Overview\" {\n            tags \"public\"\n"));
+    assert!(
+        dsl.contains("systemLandscape \"Landscape\" {\n            tags \"public\" \"summary\"\n")
+    );
+    assert!(!dsl.contains("Internals"));
+}
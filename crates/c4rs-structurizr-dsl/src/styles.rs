@@ -1,6 +1,8 @@
+use crate::error::DslError;
 use bon::Builder;
 
-#[derive(Debug, Clone, Builder)]
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ElementStyle {
     pub identifier: String,
     pub background: Option<String>,
@@ -9,16 +11,164 @@ pub struct ElementStyle {
     pub size: Option<String>,
     pub stroke: Option<String>,
     pub stroke_width: Option<String>,
+    pub border: Option<BorderStyle>,
+    /// Whether to show the element's metadata (type and technology).
+    /// `None` inherits the default.
+    pub metadata: Option<bool>,
+    /// Whether to show the element's description. `None` inherits the default.
+    pub description: Option<bool>,
 }
 
-#[derive(Debug, Clone, Builder)]
+impl<S: element_style_builder::State> ElementStyleBuilder<S> {
+    /// Sets the shape from a known [`Shape`], avoiding typos that the string
+    /// setter would pass through unchecked.
+    pub fn shape_enum(self, shape: Shape) -> ElementStyleBuilder<element_style_builder::SetShape<S>>
+    where
+        S::Shape: element_style_builder::IsUnset,
+    {
+        self.shape(shape.to_string())
+    }
+}
+
+/// Element shapes supported by Structurizr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Shape {
+    Box,
+    RoundedBox,
+    Circle,
+    Ellipse,
+    Hexagon,
+    Cylinder,
+    Pipe,
+    Person,
+    Robot,
+    Folder,
+    WebBrowser,
+    MobileDevicePortrait,
+    Component,
+    Window,
+}
+
+impl std::fmt::Display for Shape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Shape::Box => write!(f, "Box"),
+            Shape::RoundedBox => write!(f, "RoundedBox"),
+            Shape::Circle => write!(f, "Circle"),
+            Shape::Ellipse => write!(f, "Ellipse"),
+            Shape::Hexagon => write!(f, "Hexagon"),
+            Shape::Cylinder => write!(f, "Cylinder"),
+            Shape::Pipe => write!(f, "Pipe"),
+            Shape::Person => write!(f, "Person"),
+            Shape::Robot => write!(f, "Robot"),
+            Shape::Folder => write!(f, "Folder"),
+            Shape::WebBrowser => write!(f, "WebBrowser"),
+            Shape::MobileDevicePortrait => write!(f, "MobileDevicePortrait"),
+            Shape::Component => write!(f, "Component"),
+            Shape::Window => write!(f, "Window"),
+        }
+    }
+}
+
+/// Line style of an element's border, independent of its stroke colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BorderStyle {
+    Solid,
+    Dashed,
+    Dotted,
+}
+
+impl std::fmt::Display for BorderStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BorderStyle::Solid => write!(f, "solid"),
+            BorderStyle::Dashed => write!(f, "dashed"),
+            BorderStyle::Dotted => write!(f, "dotted"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RelationshipStyle {
     /// The tag this style applies to. Defaults to `"Relationship"` which
     /// targets all relationships (the implicit tag in Structurizr DSL).
-    #[builder(default = "Relationship".to_string())]
+    #[builder(default = default_relationship_identifier())]
+    #[cfg_attr(feature = "serde", serde(default = "default_relationship_identifier"))]
     pub identifier: String,
     pub thickness: Option<String>,
     pub color: Option<String>,
     pub router: Option<String>,
     pub dashed: Option<bool>,
+    pub font_size: Option<u32>,
+    /// Opacity as a percentage. The setter and deserialization fail for
+    /// values above 100.
+    #[builder(with = |opacity: u8| -> Result<_, DslError> { validate_opacity(opacity) })]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "deserialize_opacity")
+    )]
+    pub opacity: Option<u8>,
+}
+
+fn validate_opacity(opacity: u8) -> Result<u8, DslError> {
+    if opacity > 100 {
+        return Err(DslError::InvalidStyleValue {
+            property: "opacity".to_string(),
+            value: opacity.to_string(),
+        });
+    }
+    Ok(opacity)
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_opacity<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+
+    Option::<u8>::deserialize(deserializer)?
+        .map(validate_opacity)
+        .transpose()
+        .map_err(serde::de::Error::custom)
+}
+
+impl<S: relationship_style_builder::State> RelationshipStyleBuilder<S> {
+    /// Sets the router from a known [`Router`], avoiding typos that the
+    /// string setter would pass through unchecked.
+    pub fn router_enum(
+        self,
+        router: Router,
+    ) -> RelationshipStyleBuilder<relationship_style_builder::SetRouter<S>>
+    where
+        S::Router: relationship_style_builder::IsUnset,
+    {
+        self.router(router.to_string())
+    }
+}
+
+/// Relationship routing algorithms supported by Structurizr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Router {
+    Direct,
+    Orthogonal,
+    Curved,
+}
+
+impl std::fmt::Display for Router {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Router::Direct => write!(f, "Direct"),
+            Router::Orthogonal => write!(f, "Orthogonal"),
+            Router::Curved => write!(f, "Curved"),
+        }
+    }
+}
+
+fn default_relationship_identifier() -> String {
+    "Relationship".to_string()
 }
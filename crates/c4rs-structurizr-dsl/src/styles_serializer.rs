@@ -3,7 +3,7 @@ use crate::templates::view::{ElementStyleTemplate, RelationshipStyleTemplate};
 use crate::writer;
 use askama::Template;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct StylesSerializer {
     element_styles: Vec<ElementStyle>,
     relationship_styles: Vec<RelationshipStyle>,
@@ -19,16 +19,48 @@ impl StylesSerializer {
         }
     }
 
+    /// Adds an element style, replacing any existing style for the same
+    /// tag in place.
     pub fn add_element_style(mut self, style: ElementStyle) -> Self {
-        self.element_styles.push(style);
+        match self
+            .element_styles
+            .iter_mut()
+            .find(|s| s.identifier == style.identifier)
+        {
+            Some(existing) => *existing = style,
+            None => self.element_styles.push(style),
+        }
         self
     }
 
+    /// Removes the element style for the given tag, if any.
+    pub fn remove_element_style(mut self, tag: &str) -> Self {
+        self.element_styles.retain(|s| s.identifier != tag);
+        self
+    }
+
+    /// Adds a relationship style, replacing any existing style for the same
+    /// tag in place.
     pub fn add_relationship_style(mut self, style: RelationshipStyle) -> Self {
-        self.relationship_styles.push(style);
+        match self
+            .relationship_styles
+            .iter_mut()
+            .find(|s| s.identifier == style.identifier)
+        {
+            Some(existing) => *existing = style,
+            None => self.relationship_styles.push(style),
+        }
         self
     }
 
+    pub fn element_styles(&self) -> &[ElementStyle] {
+        &self.element_styles
+    }
+
+    pub fn relationship_styles(&self) -> &[RelationshipStyle] {
+        &self.relationship_styles
+    }
+
     pub fn set_external_output(mut self, output: String) -> Self {
         self.external_output = Some(output);
         self
@@ -52,6 +84,9 @@ impl StylesSerializer {
         lines.push("styles {".to_string());
 
         for style in &self.element_styles {
+            let border_str = style.border.map(|b| b.to_string());
+            let metadata_str = style.metadata.map(|m| m.to_string());
+            let description_str = style.description.map(|d| d.to_string());
             let template = ElementStyleTemplate {
                 identifier: &style.identifier,
                 background: style.background.as_deref(),
@@ -60,6 +95,9 @@ impl StylesSerializer {
                 size: style.size.as_deref(),
                 stroke: style.stroke.as_deref(),
                 stroke_width: style.stroke_width.as_deref(),
+                border: border_str.as_deref(),
+                metadata: metadata_str.as_deref(),
+                description: description_str.as_deref(),
             };
             lines.push(template.render()?);
         }
@@ -72,12 +110,16 @@ impl StylesSerializer {
                     "false".to_string()
                 }
             });
+            let font_size_str = style.font_size.map(|f| f.to_string());
+            let opacity_str = style.opacity.map(|o| o.to_string());
             let template = RelationshipStyleTemplate {
                 identifier: &style.identifier,
                 thickness: style.thickness.as_deref(),
                 color: style.color.as_deref(),
                 router: style.router.as_deref(),
                 dashed: dashed_str.as_deref(),
+                font_size: font_size_str.as_deref(),
+                opacity: opacity_str.as_deref(),
             };
             lines.push(template.render()?);
         }
@@ -1,4 +1,6 @@
 use super::*;
+use crate::error::DslError;
+use crate::styles::{BorderStyle, Router, Shape};
 
 #[test]
 fn test_element_style() {
@@ -116,3 +118,192 @@ fn test_us5_relationship_style_from_spec() {
     assert!(dsl.contains(r#"relationship "Relationship" {"#));
     assert!(dsl.contains("thickness 4"));
 }
+
+#[test]
+fn test_element_style_metadata_and_description_toggles() {
+    let styles = StylesSerializer::new()
+        .add_element_style(
+            ElementStyle::builder()
+                .identifier("Container".into())
+                .metadata(false)
+                .build(),
+        )
+        .add_element_style(
+            ElementStyle::builder()
+                .identifier("Component".into())
+                .description(false)
+                .build(),
+        );
+
+    let dsl = styles.serialize().unwrap();
+    assert!(dsl.contains(
+        r#"    element "Container" {
+        metadata false
+    }"#
+    ));
+    assert!(dsl.contains(
+        r#"    element "Component" {
+        description false
+    }"#
+    ));
+}
+
+#[test]
+fn test_element_style_border_variants() {
+    for (border, expected) in [
+        (BorderStyle::Solid, "border solid"),
+        (BorderStyle::Dashed, "border dashed"),
+        (BorderStyle::Dotted, "border dotted"),
+    ] {
+        let styles = StylesSerializer::new().add_element_style(
+            ElementStyle::builder()
+                .identifier("External".into())
+                .stroke("#999999".into())
+                .border(border)
+                .build(),
+        );
+
+        let dsl = styles.serialize().unwrap();
+        assert!(dsl.contains(&format!("        stroke #999999\n        {}\n", expected)));
+    }
+}
+
+#[test]
+fn test_element_style_shape_enum_variants() {
+    for (shape, expected) in [
+        (Shape::Box, "Box"),
+        (Shape::RoundedBox, "RoundedBox"),
+        (Shape::Circle, "Circle"),
+        (Shape::Ellipse, "Ellipse"),
+        (Shape::Hexagon, "Hexagon"),
+        (Shape::Cylinder, "Cylinder"),
+        (Shape::Pipe, "Pipe"),
+        (Shape::Person, "Person"),
+        (Shape::Robot, "Robot"),
+        (Shape::Folder, "Folder"),
+        (Shape::WebBrowser, "WebBrowser"),
+        (Shape::MobileDevicePortrait, "MobileDevicePortrait"),
+        (Shape::Component, "Component"),
+        (Shape::Window, "Window"),
+    ] {
+        let style = ElementStyle::builder()
+            .identifier("Element".into())
+            .shape_enum(shape)
+            .build();
+        assert_eq!(style.shape.as_deref(), Some(expected));
+
+        let dsl = StylesSerializer::new()
+            .add_element_style(style)
+            .serialize()
+            .unwrap();
+        assert!(dsl.contains(&format!("        shape {}\n", expected)));
+    }
+}
+
+#[test]
+fn test_relationship_style_font_size_and_opacity() {
+    let style = RelationshipStyle::builder()
+        .font_size(24)
+        .opacity(50)
+        .unwrap()
+        .build();
+    let dsl = StylesSerializer::new()
+        .add_relationship_style(style)
+        .serialize()
+        .unwrap();
+
+    assert!(dsl.contains(
+        "    relationship \"Relationship\" {\n        fontSize 24\n        opacity 50\n    }"
+    ));
+}
+
+#[test]
+fn test_relationship_style_opacity_out_of_range() {
+    let result = RelationshipStyle::builder().opacity(101);
+    assert!(matches!(
+        result,
+        Err(DslError::InvalidStyleValue { property, value }) if property == "opacity" && value == "101"
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_relationship_style_opacity_validated_on_deserialize() {
+    let parsed: RelationshipStyle = serde_json::from_str(r#"{"opacity": 40}"#).unwrap();
+    assert_eq!(parsed.opacity, Some(40));
+
+    let parsed: RelationshipStyle = serde_json::from_str("{}").unwrap();
+    assert_eq!(parsed.opacity, None);
+
+    let err = serde_json::from_str::<RelationshipStyle>(r#"{"opacity": 101}"#).unwrap_err();
+    assert!(err.to_string().contains("opacity"));
+}
+
+#[test]
+fn test_element_style_override_and_remove() {
+    let style = |background: &str| {
+        ElementStyle::builder()
+            .identifier("Person".into())
+            .background(background.into())
+            .build()
+    };
+    let styles = StylesSerializer::new()
+        .add_element_style(style("#111111"))
+        .add_element_style(
+            ElementStyle::builder()
+                .identifier("Database".into())
+                .shape("Cylinder".into())
+                .build(),
+        )
+        .add_element_style(style("#222222"));
+
+    let dsl = styles.serialize().unwrap();
+    assert_eq!(dsl.matches(r#"element "Person""#).count(), 1);
+    assert!(dsl.contains("background #222222"));
+    assert!(!dsl.contains("background #111111"));
+    assert!(dsl.find(r#"element "Person""#) < dsl.find(r#"element "Database""#));
+
+    let dsl = styles.remove_element_style("Person").serialize().unwrap();
+    assert!(!dsl.contains(r#"element "Person""#));
+    assert!(dsl.contains(r#"element "Database""#));
+}
+
+#[test]
+fn test_adding_identical_styles_is_idempotent() {
+    let person = ElementStyle::builder()
+        .identifier("Person".into())
+        .shape_enum(Shape::Person)
+        .build();
+    let relationship = RelationshipStyle::builder().thickness("2".into()).build();
+
+    let once = StylesSerializer::new()
+        .add_element_style(person.clone())
+        .add_relationship_style(relationship.clone());
+    let twice = StylesSerializer::new()
+        .add_element_style(person.clone())
+        .add_element_style(person.clone())
+        .add_relationship_style(relationship.clone())
+        .add_relationship_style(relationship.clone());
+
+    assert_eq!(twice.element_styles(), [person]);
+    assert_eq!(twice.relationship_styles(), [relationship]);
+    assert_eq!(twice.serialize().unwrap(), once.serialize().unwrap());
+}
+
+#[test]
+fn test_relationship_style_router_enum_variants() {
+    for (router, expected) in [
+        (Router::Direct, "Direct"),
+        (Router::Orthogonal, "Orthogonal"),
+        (Router::Curved, "Curved"),
+    ] {
+        let style = RelationshipStyle::builder().router_enum(router).build();
+        assert_eq!(style.router.as_deref(), Some(expected));
+
+        let dsl = StylesSerializer::new()
+            .add_relationship_style(style)
+            .serialize()
+            .unwrap();
+        assert!(dsl.contains(&format!("        router {}\n", expected)));
+    }
+}
@@ -1,5 +1,12 @@
+/// Escapes a value for use inside a quoted DSL string. A literal `${` is
+/// escaped as `\${` so Structurizr does not treat it as variable
+/// interpolation.
 pub fn escape_dsl_string(s: &str) -> String {
-    s.replace('\\', "\\\\").replace('"', "\\\"")
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n")
+        .replace("${", "\\${")
 }
 
 pub fn format_identifier(name: &str) -> String {
@@ -2,8 +2,11 @@ use askama::Template;
 
 #[derive(Template)]
 #[template(
-    source = r#"    {{ view_type }}{% if let Some(id) = identifier %} {{ id }}{% endif %} "{{ title }}" {
-{% for inc in include_elements %}        include {{ inc }}
+    source = r#"    {{ view_type }}{% if let Some(id) = identifier %} {{ id }}{% endif %}{% if let Some(env) = environment %} "{{ env }}"{% endif %} "{{ key }}" {
+{% if is_default %}        default
+{% endif %}{% if let Some(t) = title %}        title "{{ t }}"
+{% endif %}{% if !tags.is_empty() %}        tags{% for tag in tags %} "{{ tag }}"{% endfor %}
+{% endif %}{% for inc in include_elements %}        include {{ inc }}
 {% endfor %}{% for exc in exclude_elements %}        exclude {{ exc }}
 {% endfor %}    }"#,
     ext = "txt"
@@ -11,7 +14,11 @@ use askama::Template;
 pub struct ViewTemplate<'a> {
     pub view_type: &'a str,
     pub identifier: Option<&'a str>,
-    pub title: &'a str,
+    pub environment: Option<&'a str>,
+    pub key: &'a str,
+    pub title: Option<&'a str>,
+    pub is_default: bool,
+    pub tags: &'a [&'a str],
     pub include_elements: &'a [&'a str],
     pub exclude_elements: &'a [&'a str],
 }
@@ -25,6 +32,9 @@ pub struct ViewTemplate<'a> {
 {% endif %}{% if let Some(sz) = size %}        size {{ sz }}
 {% endif %}{% if let Some(st) = stroke %}        stroke {{ st }}
 {% endif %}{% if let Some(sw) = stroke_width %}        strokeWidth {{ sw }}
+{% endif %}{% if let Some(b) = border %}        border {{ b }}
+{% endif %}{% if let Some(m) = metadata %}        metadata {{ m }}
+{% endif %}{% if let Some(d) = description %}        description {{ d }}
 {% endif %}    }"#,
     ext = "txt"
 )]
@@ -36,6 +46,9 @@ pub struct ElementStyleTemplate<'a> {
     pub size: Option<&'a str>,
     pub stroke: Option<&'a str>,
     pub stroke_width: Option<&'a str>,
+    pub border: Option<&'a str>,
+    pub metadata: Option<&'a str>,
+    pub description: Option<&'a str>,
 }
 
 #[derive(Template)]
@@ -45,6 +58,8 @@ pub struct ElementStyleTemplate<'a> {
 {% endif %}{% if let Some(c) = color %}        color {{ c }}
 {% endif %}{% if let Some(r) = router %}        router {{ r }}
 {% endif %}{% if let Some(d) = dashed %}        dashed {{ d }}
+{% endif %}{% if let Some(f) = font_size %}        fontSize {{ f }}
+{% endif %}{% if let Some(o) = opacity %}        opacity {{ o }}
 {% endif %}    }"#,
     ext = "txt"
 )]
@@ -54,4 +69,6 @@ pub struct RelationshipStyleTemplate<'a> {
     pub color: Option<&'a str>,
     pub router: Option<&'a str>,
     pub dashed: Option<&'a str>,
+    pub font_size: Option<&'a str>,
+    pub opacity: Option<&'a str>,
 }
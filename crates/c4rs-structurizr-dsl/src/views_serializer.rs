@@ -1,11 +1,14 @@
 use crate::error::DslError;
+use crate::templates::helpers::escape_dsl_string;
 use crate::templates::view::ViewTemplate;
 use crate::writer;
 use askama::Template;
 use bon::Builder;
+use c4rs_core::c4::{Element, ElementId, ElementType};
 
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ViewType {
     #[default]
     SystemContext,
@@ -43,17 +46,95 @@ impl std::fmt::Display for ViewType {
 }
 
 #[derive(Debug, Clone, Builder)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ViewConfiguration {
     pub view_type: ViewType,
     pub element_identifier: String,
     pub title: String,
+    /// Machine key of the view. When set, it is used verbatim and `title`
+    /// is emitted as a separate `title` line; otherwise the key is derived
+    /// from the title.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub key: Option<String>,
     #[builder(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub include_elements: Vec<String>,
     #[builder(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub exclude_elements: Vec<String>,
+    /// Deployment environment name; only used by deployment views.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub environment: Option<String>,
+    #[builder(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tags: Vec<String>,
+    /// Element the view was created for with [`default_for`](Self::default_for);
+    /// the serializer replaces `element_identifier` with its resolved path.
+    #[builder(skip)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    scope: Option<ElementId>,
+    /// Elements included by id, appended to `include_elements` as their
+    /// resolved paths at serialization time.
+    #[builder(skip)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    included: Vec<ElementId>,
 }
 
 impl ViewConfiguration {
+    /// Creates a view of `view_type` scoped to `element`, titled after the
+    /// element's name (e.g. "API Components"). The scope is resolved to the
+    /// element's DSL identifier at serialization time, so nested elements
+    /// get their full hierarchical path.
+    pub fn default_for(element: &impl Element, view_type: ViewType) -> Self {
+        let label = match view_type {
+            ViewType::SystemContext => "System Context",
+            ViewType::Container => "Containers",
+            ViewType::Component => "Components",
+            ViewType::SystemLandscape => "Landscape",
+            ViewType::Filtered => "Filtered",
+            ViewType::Dynamic => "Dynamic",
+            ViewType::Deployment => "Deployment",
+            ViewType::Custom => "Custom",
+        };
+        let mut view = Self::builder()
+            .view_type(view_type)
+            .element_identifier(element.id().as_str().to_string())
+            .title(format!("{} {}", element.name(), label))
+            .build();
+        view.scope = Some(element.id().clone());
+        view
+    }
+
+    /// Returns the element this view was created for with
+    /// [`default_for`](Self::default_for), if any.
+    pub fn scope(&self) -> Option<&ElementId> {
+        self.scope.as_ref()
+    }
+
+    /// Scopes the view to an element by id, resolved when serializing.
+    #[cfg(feature = "json")]
+    pub(crate) fn with_scope(mut self, id: ElementId) -> Self {
+        self.scope = Some(id);
+        self
+    }
+
+    /// Includes elements by id, resolved when serializing.
+    #[cfg(feature = "json")]
+    pub(crate) fn include_ids(mut self, ids: impl IntoIterator<Item = ElementId>) -> Self {
+        self.included.extend(ids);
+        self
+    }
+
+    /// Replaces id-based references with DSL paths from `resolve`.
+    pub(crate) fn resolve_references(&mut self, resolve: impl Fn(&ElementId) -> String) {
+        if let Some(id) = &self.scope {
+            self.element_identifier = resolve(id);
+        }
+        for id in std::mem::take(&mut self.included) {
+            push_unique(&mut self.include_elements, resolve(&id));
+        }
+    }
+
     /// Returns the element identifier for DSL output, or `None` if this
     /// view type does not use one (e.g. `systemLandscape`).
     pub fn dsl_identifier(&self) -> Option<&str> {
@@ -64,27 +145,224 @@ impl ViewConfiguration {
         }
     }
 
-    /// Returns the title formatted as a valid DSL view key
-    /// (spaces replaced with underscores).
+    /// Returns the deployment environment for DSL output, or `None` for
+    /// views other than `deployment`.
+    pub fn dsl_environment(&self) -> Option<&str> {
+        if self.view_type == ViewType::Deployment {
+            self.environment.as_deref()
+        } else {
+            None
+        }
+    }
+
+    /// Adds an `include` expression such as `*` or an element identifier.
+    /// Exact duplicates are ignored.
+    pub fn include_element(mut self, expression: &str) -> Self {
+        push_unique(&mut self.include_elements, expression.to_string());
+        self
+    }
+
+    /// Adds an `exclude` expression. Exact duplicates are ignored.
+    pub fn exclude_element(mut self, expression: &str) -> Self {
+        push_unique(&mut self.exclude_elements, expression.to_string());
+        self
+    }
+
+    /// Adds several `include` expressions at once. Exact duplicates are
+    /// ignored.
+    pub fn include_elements<I, S>(mut self, expressions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        for expression in expressions {
+            push_unique(&mut self.include_elements, expression.into());
+        }
+        self
+    }
+
+    /// Adds several `exclude` expressions at once. Exact duplicates are
+    /// ignored.
+    pub fn exclude_elements<I, S>(mut self, expressions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        for expression in expressions {
+            push_unique(&mut self.exclude_elements, expression.into());
+        }
+        self
+    }
+
+    /// Includes every element of the given type via an `element.type==`
+    /// expression. Code elements have no Structurizr element type and are
+    /// ignored.
+    pub fn include_by_type(mut self, element_type: ElementType) -> Self {
+        if let Some(expression) = element_type_expression(&element_type) {
+            push_unique(&mut self.include_elements, expression);
+        }
+        self
+    }
+
+    /// Excludes every element of the given type via an `element.type==`
+    /// expression. Code elements have no Structurizr element type and are
+    /// ignored.
+    pub fn exclude_by_type(mut self, element_type: ElementType) -> Self {
+        if let Some(expression) = element_type_expression(&element_type) {
+            push_unique(&mut self.exclude_elements, expression);
+        }
+        self
+    }
+
+    /// Includes the direct children of the element with the given DSL
+    /// identifier via an `element.parent==` expression, e.g. only one
+    /// system's containers. The identifier is escaped like other quoted
+    /// values.
+    pub fn include_children_of(mut self, parent_id: &str) -> Self {
+        push_unique(
+            &mut self.include_elements,
+            format!("\"element.parent=={}\"", escape_dsl_string(parent_id)),
+        );
+        self
+    }
+
+    /// Includes every relationship carrying the given tag via a
+    /// `relationship.tag==` expression.
+    pub fn include_relationships_by_tag(mut self, tag: &str) -> Self {
+        push_unique(&mut self.include_elements, relationship_tag_expression(tag));
+        self
+    }
+
+    /// Excludes every relationship carrying the given tag via a
+    /// `relationship.tag==` expression.
+    pub fn exclude_relationships_by_tag(mut self, tag: &str) -> Self {
+        push_unique(&mut self.exclude_elements, relationship_tag_expression(tag));
+        self
+    }
+
+    /// Returns the view key: the explicit [`key`](Self::key) if set,
+    /// otherwise the title with spaces replaced by underscores.
+    pub fn dsl_key(&self) -> String {
+        match &self.key {
+            Some(key) => key.clone(),
+            None => self.title.replace(' ', "_"),
+        }
+    }
+
+    #[deprecated(note = "use `dsl_key`")]
     pub fn dsl_title(&self) -> String {
-        self.title.replace(' ', "_")
+        self.dsl_key()
     }
 }
 
-#[derive(Debug, Default, Builder)]
+/// Builds the quoted Structurizr expression matching all elements of a type.
+fn element_type_expression(element_type: &ElementType) -> Option<String> {
+    let keyword = match element_type {
+        ElementType::Person => "Person",
+        ElementType::SoftwareSystem => "SoftwareSystem",
+        ElementType::Container => "Container",
+        ElementType::Component => "Component",
+        ElementType::Code => return None,
+    };
+    Some(format!("\"element.type=={}\"", keyword))
+}
+
+/// Builds the quoted Structurizr expression matching relationships by tag.
+fn relationship_tag_expression(tag: &str) -> String {
+    format!("\"relationship.tag=={}\"", escape_dsl_string(tag))
+}
+
+/// Where the `styles` block is placed relative to the view definitions
+/// inside the `views` block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StylesPosition {
+    Before,
+    #[default]
+    After,
+}
+
+#[derive(Debug, Builder)]
 pub struct ViewsSerializer {
     #[builder(default)]
     views: Vec<ViewConfiguration>,
+    #[builder(default)]
+    styles_position: StylesPosition,
+    /// Emit `include *` for views without any include expressions.
+    #[builder(default = true)]
+    auto_include: bool,
+    /// Key of the view shown first when the workspace is opened.
+    default_view: Option<String>,
+    /// External theme URLs, written as `theme` or `themes`.
+    #[builder(default)]
+    themes: Vec<String>,
     external_output: Option<String>,
     styles_output: Option<String>,
     configuration_output: Option<String>,
 }
 
+impl Default for ViewsSerializer {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
 impl ViewsSerializer {
     pub fn add_view(&mut self, view: ViewConfiguration) {
         self.views.push(view);
     }
 
+    /// Keeps only the views whose DSL key (see [`ViewConfiguration::dsl_key`])
+    /// is in `keys`. Fails without modifying the views if a key is unknown.
+    pub fn retain_views(&mut self, keys: &[&str]) -> Result<(), DslError> {
+        if let Some(unknown) = keys
+            .iter()
+            .find(|key| !self.views.iter().any(|v| v.dsl_key() == **key))
+        {
+            return Err(DslError::UnknownViewKey {
+                key: unknown.to_string(),
+            });
+        }
+        self.retain(|v| keys.contains(&v.dsl_key().as_str()));
+        Ok(())
+    }
+
+    /// Keeps only the views carrying the given tag.
+    pub fn retain_views_tagged(&mut self, tag: &str) {
+        self.retain(|v| v.tags.iter().any(|t| t == tag));
+    }
+
+    /// Retains views matching `keep`, dropping the default view marker if
+    /// its view was removed.
+    fn retain(&mut self, keep: impl Fn(&ViewConfiguration) -> bool) {
+        self.views.retain(&keep);
+        if let Some(key) = &self.default_view
+            && !self.views.iter().any(|v| &v.dsl_key() == key)
+        {
+            self.default_view = None;
+        }
+    }
+
+    pub fn set_styles_position(&mut self, position: StylesPosition) {
+        self.styles_position = position;
+    }
+
+    pub fn set_auto_include(&mut self, auto_include: bool) {
+        self.auto_include = auto_include;
+    }
+
+    pub fn set_default_view(&mut self, key: String) {
+        self.default_view = Some(key);
+    }
+
+    /// Adds an external theme URL. Duplicates are ignored.
+    pub fn add_theme(&mut self, url: String) {
+        push_unique(&mut self.themes, url);
+    }
+
+    pub fn themes(&self) -> &[String] {
+        &self.themes
+    }
+
     pub fn set_external_output(&mut self, output: String) {
         self.external_output = Some(output);
     }
@@ -97,6 +375,14 @@ impl ViewsSerializer {
         self.configuration_output = Some(output);
     }
 
+    pub fn views(&self) -> &[ViewConfiguration] {
+        &self.views
+    }
+
+    pub(crate) fn views_mut(&mut self) -> &mut [ViewConfiguration] {
+        &mut self.views
+    }
+
     pub fn styles_output(&self) -> Option<&String> {
         self.styles_output.as_ref()
     }
@@ -110,9 +396,16 @@ impl ViewsSerializer {
             return Ok(output);
         }
 
+        if let Some(key) = &self.default_view
+            && !self.views.iter().any(|v| &v.dsl_key() == key)
+        {
+            return Err(DslError::UnknownViewKey { key: key.clone() });
+        }
+
         if self.views.is_empty()
             && self.styles_output.is_none()
             && self.configuration_output.is_none()
+            && self.themes.is_empty()
         {
             return Ok(String::new());
         }
@@ -120,30 +413,69 @@ impl ViewsSerializer {
         let mut lines = Vec::new();
         lines.push("views {".to_string());
 
+        if self.styles_position == StylesPosition::Before
+            && let Some(ref styles) = self.styles_output
+        {
+            for line in styles.lines() {
+                lines.push(line.to_string());
+            }
+            if !self.views.is_empty() {
+                lines.push(String::new());
+            }
+        }
+
         for view in &self.views {
-            let include_refs: Vec<&str> =
-                view.include_elements.iter().map(|s| s.as_str()).collect();
-            let exclude_refs: Vec<&str> =
-                view.exclude_elements.iter().map(|s| s.as_str()).collect();
-            let title = view.dsl_title();
+            let include_refs: Vec<&str> = if view.include_elements.is_empty() && self.auto_include {
+                vec!["*"]
+            } else {
+                unique_refs(&view.include_elements)
+            };
+            let exclude_refs = unique_refs(&view.exclude_elements);
+            let key = view.dsl_key();
+            if view.view_type == ViewType::Deployment && view.environment.is_none() {
+                return Err(DslError::MissingDeploymentEnvironment { view: key });
+            }
+            let title = view.key.as_ref().map(|_| escape_dsl_string(&view.title));
+            let environment = view.dsl_environment().map(escape_dsl_string);
+            let tags: Vec<String> = view.tags.iter().map(|t| escape_dsl_string(t)).collect();
+            let tag_refs: Vec<&str> = tags.iter().map(|s| s.as_str()).collect();
 
             let template = ViewTemplate {
                 view_type: &view.view_type.to_string(),
                 identifier: view.dsl_identifier(),
-                title: &title,
+                environment: environment.as_deref(),
+                key: &key,
+                title: title.as_deref(),
+                is_default: self.default_view.as_ref() == Some(&key),
+                tags: &tag_refs,
                 include_elements: &include_refs,
                 exclude_elements: &exclude_refs,
             };
             lines.push(template.render()?);
         }
 
-        if let Some(ref styles) = self.styles_output {
+        if self.styles_position == StylesPosition::After
+            && let Some(ref styles) = self.styles_output
+        {
             lines.push(String::new());
             for line in styles.lines() {
                 lines.push(line.to_string());
             }
         }
 
+        if !self.themes.is_empty() {
+            let quoted: Vec<String> = self
+                .themes
+                .iter()
+                .map(|url| format!(r#""{}""#, escape_dsl_string(url)))
+                .collect();
+            let keyword = if quoted.len() == 1 { "theme" } else { "themes" };
+            if lines.len() > 1 {
+                lines.push(String::new());
+            }
+            lines.push(format!("    {} {}", keyword, quoted.join(" ")));
+        }
+
         if let Some(ref config) = self.configuration_output {
             lines.push(String::new());
             for line in config.lines() {
@@ -156,6 +488,24 @@ impl ViewsSerializer {
     }
 }
 
+fn push_unique(expressions: &mut Vec<String>, expression: String) {
+    if !expressions.contains(&expression) {
+        expressions.push(expression);
+    }
+}
+
+/// Drops repeated expressions, e.g. from fields set directly, keeping the
+/// first occurrence of each.
+fn unique_refs(expressions: &[String]) -> Vec<&str> {
+    let mut refs: Vec<&str> = Vec::with_capacity(expressions.len());
+    for expression in expressions {
+        if !refs.contains(&expression.as_str()) {
+            refs.push(expression);
+        }
+    }
+    refs
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,6 +527,99 @@ mod tests {
         assert!(dsl.contains("include *"));
     }
 
+    #[test]
+    fn test_landscape_view_has_no_stray_space() {
+        let mut views = ViewsSerializer::builder().build();
+        for (identifier, key) in [("*", None), ("", Some("Overview"))] {
+            views.add_view(
+                ViewConfiguration::builder()
+                    .view_type(ViewType::SystemLandscape)
+                    .element_identifier(identifier.to_string())
+                    .title("Landscape".to_string())
+                    .maybe_key(key.map(str::to_string))
+                    .build(),
+            );
+        }
+
+        let dsl = views.serialize().unwrap();
+        assert!(dsl.contains(r#"    systemLandscape "Landscape" {"#));
+        assert!(dsl.contains(r#"    systemLandscape "Overview" {"#));
+        assert!(!dsl.contains("systemLandscape  "));
+    }
+
+    #[test]
+    fn test_include_and_exclude_deduplicated() {
+        let view = ViewConfiguration::builder()
+            .view_type(ViewType::SystemContext)
+            .element_identifier("a".to_string())
+            .title("System Context".to_string())
+            .exclude_elements(vec!["b".to_string(), "c".to_string(), "b".to_string()])
+            .build()
+            .include_element("*")
+            .include_element("*")
+            .exclude_element("c");
+        assert_eq!(view.include_elements, vec!["*"]);
+
+        let mut views = ViewsSerializer::builder().build();
+        views.add_view(view);
+        let dsl = views.serialize().unwrap();
+        assert_eq!(dsl.matches("include *").count(), 1);
+        assert_eq!(dsl.matches("exclude b").count(), 1);
+        assert!(dsl.find("exclude b").unwrap() < dsl.find("exclude c").unwrap());
+    }
+
+    #[test]
+    fn test_includes_rendered_before_excludes() {
+        let mut views = ViewsSerializer::builder().build();
+        views.add_view(
+            ViewConfiguration::builder()
+                .view_type(ViewType::Container)
+                .element_identifier("s".to_string())
+                .title("Containers".to_string())
+                .build()
+                .exclude_element("s.db")
+                .include_element("*")
+                .exclude_element("s.cache")
+                .include_element("u"),
+        );
+
+        let dsl = views.serialize().unwrap();
+        assert!(dsl.contains(
+            "        include *\n        include u\n        exclude s.db\n        exclude s.cache\n"
+        ));
+    }
+
+    #[test]
+    fn test_themes() {
+        let mut views = ViewsSerializer::builder().build();
+        views.add_theme("https://static.structurizr.com/themes/default/theme.json".to_string());
+        assert_eq!(
+            views.serialize().unwrap(),
+            "views {\n    theme \"https://static.structurizr.com/themes/default/theme.json\"\n}"
+        );
+
+        views.add_theme("https://example.com/corporate.json".to_string());
+        views.add_theme("https://example.com/corporate.json".to_string());
+        assert!(views.serialize().unwrap().contains(
+            "    themes \"https://static.structurizr.com/themes/default/theme.json\" \"https://example.com/corporate.json\"\n"
+        ));
+    }
+
+    #[test]
+    fn test_bulk_include_and_exclude() {
+        let view = ViewConfiguration::builder()
+            .view_type(ViewType::Container)
+            .element_identifier("s".to_string())
+            .title("Containers".to_string())
+            .build()
+            .include_element("u")
+            .include_elements(["s.web", "s.api", "u"])
+            .include_elements(vec![String::from("s.db")])
+            .exclude_elements(["s.cache"]);
+        assert_eq!(view.include_elements, ["u", "s.web", "s.api", "s.db"]);
+        assert_eq!(view.exclude_elements, ["s.cache"]);
+    }
+
     #[test]
     fn test_container_view() {
         let mut views = ViewsSerializer::builder().build();
@@ -194,6 +637,212 @@ mod tests {
         assert!(dsl.contains("exclude Database"));
     }
 
+    #[test]
+    fn test_deployment_view_environment() {
+        let mut views = ViewsSerializer::builder().build();
+        views.add_view(
+            ViewConfiguration::builder()
+                .view_type(ViewType::Deployment)
+                .element_identifier("s".to_string())
+                .environment("Production".to_string())
+                .title("Live".to_string())
+                .build(),
+        );
+        views.add_view(
+            ViewConfiguration::builder()
+                .view_type(ViewType::Container)
+                .element_identifier("s".to_string())
+                .environment("Production".to_string())
+                .title("Containers".to_string())
+                .build(),
+        );
+
+        let dsl = views.serialize().unwrap();
+        assert!(dsl.contains(r#"deployment s "Production" "Live" {"#));
+        assert!(dsl.contains(r#"container s "Containers" {"#));
+    }
+
+    #[test]
+    fn test_deployment_view_without_environment_is_rejected() {
+        let mut views = ViewsSerializer::builder().build();
+        views.add_view(
+            ViewConfiguration::builder()
+                .view_type(ViewType::Deployment)
+                .element_identifier("s".to_string())
+                .title("Live".to_string())
+                .build(),
+        );
+
+        assert!(matches!(
+            views.serialize(),
+            Err(DslError::MissingDeploymentEnvironment { .. })
+        ));
+    }
+
+    #[test]
+    fn test_styles_before_views() {
+        let mut views = ViewsSerializer::builder()
+            .styles_position(StylesPosition::Before)
+            .build();
+        views.add_view(
+            ViewConfiguration::builder()
+                .view_type(ViewType::SystemContext)
+                .element_identifier("a".to_string())
+                .title("Context".to_string())
+                .build(),
+        );
+        views.set_styles_output("styles {\n}".to_string());
+
+        let dsl = views.serialize().unwrap();
+        assert_eq!(
+            dsl,
+            "views {\nstyles {\n}\n\n    systemContext a \"Context\" {\n        include *\n    }\n}"
+        );
+    }
+
+    #[test]
+    fn test_auto_include_for_empty_includes() {
+        let view = ViewConfiguration::builder()
+            .view_type(ViewType::SystemContext)
+            .element_identifier("a".to_string())
+            .title("Context".to_string())
+            .build();
+
+        let mut views = ViewsSerializer::builder().build();
+        views.add_view(view.clone());
+        assert!(views.serialize().unwrap().contains("        include *\n"));
+
+        let mut views = ViewsSerializer::builder().auto_include(false).build();
+        views.add_view(view);
+        assert!(!views.serialize().unwrap().contains("include"));
+    }
+
+    #[test]
+    fn test_include_and_exclude_by_type() {
+        let mut views = ViewsSerializer::builder().build();
+        let view = ViewConfiguration::builder()
+            .view_type(ViewType::Container)
+            .element_identifier("s".to_string())
+            .title("Containers".to_string())
+            .build()
+            .include_by_type(ElementType::Container)
+            .exclude_by_type(ElementType::Person)
+            .include_by_type(ElementType::Code);
+        views.add_view(view);
+
+        let dsl = views.serialize().unwrap();
+        assert!(dsl.contains(r#"include "element.type==Container""#));
+        assert!(dsl.contains(r#"exclude "element.type==Person""#));
+        assert_eq!(dsl.matches("include").count(), 1);
+    }
+
+    #[test]
+    fn test_default_view() {
+        let view = |title: &str| {
+            ViewConfiguration::builder()
+                .view_type(ViewType::SystemLandscape)
+                .element_identifier(String::new())
+                .title(title.to_string())
+                .include_elements(vec!["*".to_string()])
+                .build()
+        };
+        let mut views = ViewsSerializer::builder()
+            .default_view("Landscape".to_string())
+            .build();
+        views.add_view(view("Overview"));
+        views.add_view(view("Landscape"));
+
+        let dsl = views.serialize().unwrap();
+        assert!(
+            dsl.contains("systemLandscape \"Landscape\" {\n        default\n        include *")
+        );
+        assert_eq!(dsl.matches("default").count(), 1);
+
+        views.set_default_view("Missing".to_string());
+        assert!(matches!(
+            views.serialize(),
+            Err(DslError::UnknownViewKey { key }) if key == "Missing"
+        ));
+    }
+
+    #[test]
+    fn test_explicit_key_and_title() {
+        let mut views = ViewsSerializer::builder().build();
+        views.add_view(
+            ViewConfiguration::builder()
+                .view_type(ViewType::Container)
+                .element_identifier("s".to_string())
+                .key("shop-containers".to_string())
+                .title("Shop: Containers".to_string())
+                .include_elements(vec!["*".to_string()])
+                .build(),
+        );
+
+        let dsl = views.serialize().unwrap();
+        assert!(dsl.contains(
+            "    container s \"shop-containers\" {\n        title \"Shop: Containers\"\n        include *\n"
+        ));
+    }
+
+    #[test]
+    fn test_include_children_of() {
+        let mut views = ViewsSerializer::builder().build();
+        views.add_view(
+            ViewConfiguration::builder()
+                .view_type(ViewType::Container)
+                .element_identifier("s".to_string())
+                .title("Containers".to_string())
+                .build()
+                .include_children_of("s")
+                .include_children_of("x\" include *"),
+        );
+
+        let dsl = views.serialize().unwrap();
+        assert!(dsl.contains(r#"include "element.parent==s""#));
+        assert!(dsl.contains(r#"include "element.parent==x\" include *""#));
+        assert!(!dsl.contains("include *\n"));
+    }
+
+    #[test]
+    fn test_relationships_by_tag() {
+        let mut views = ViewsSerializer::builder().build();
+        views.add_view(
+            ViewConfiguration::builder()
+                .view_type(ViewType::Container)
+                .element_identifier("s".to_string())
+                .title("Containers".to_string())
+                .build()
+                .include_element("*")
+                .include_relationships_by_tag("Async")
+                .exclude_relationships_by_tag("Legacy"),
+        );
+
+        let dsl = views.serialize().unwrap();
+        assert!(dsl.contains(r#"include "relationship.tag==Async""#));
+        assert!(dsl.contains(r#"exclude "relationship.tag==Legacy""#));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_view_configuration_json_round_trip() {
+        let view = ViewConfiguration::builder()
+            .view_type(ViewType::Container)
+            .element_identifier("api".to_string())
+            .title("Containers".to_string())
+            .include_elements(vec!["*".to_string()])
+            .exclude_elements(vec!["db".to_string()])
+            .build();
+
+        let json = serde_json::to_string(&view).unwrap();
+        let parsed: ViewConfiguration = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.view_type, ViewType::Container);
+        assert_eq!(parsed.element_identifier, "api");
+        assert_eq!(parsed.title, "Containers");
+        assert_eq!(parsed.include_elements, vec!["*"]);
+        assert_eq!(parsed.exclude_elements, vec!["db"]);
+    }
+
     #[test]
     fn test_empty_views() {
         let views = ViewsSerializer::builder().build();
@@ -0,0 +1,431 @@
+//! Import of Structurizr `workspace.json` documents.
+//!
+//! Only the parts of the JSON schema that c4rs can represent are read:
+//! people, software systems, containers, components, their relationships,
+//! system landscape/context/container/component views, and styles. Unknown
+//! fields are ignored and missing optional fields fall back to defaults.
+
+use crate::{
+    ElementStyle, RelationshipStyle, ViewConfiguration, ViewType, WorkspaceSerializer,
+    error::DslError,
+};
+use c4rs_core::c4::{
+    Component, Container, ContainerType, ElementId, Location, Person, SoftwareSystem,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct JsonWorkspace {
+    name: Option<String>,
+    description: Option<String>,
+    model: JsonModel,
+    views: JsonViews,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct JsonModel {
+    people: Vec<JsonElement>,
+    software_systems: Vec<JsonElement>,
+}
+
+/// A model element of any kind; children only appear on the kinds that
+/// can contain them.
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct JsonElement {
+    id: String,
+    name: String,
+    description: Option<String>,
+    technology: Option<String>,
+    location: Option<String>,
+    tags: Option<String>,
+    relationships: Vec<JsonRelationship>,
+    containers: Vec<JsonElement>,
+    components: Vec<JsonElement>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct JsonRelationship {
+    source_id: String,
+    destination_id: String,
+    description: Option<String>,
+    technology: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct JsonViews {
+    system_landscape_views: Vec<JsonView>,
+    system_context_views: Vec<JsonView>,
+    container_views: Vec<JsonView>,
+    component_views: Vec<JsonView>,
+    configuration: JsonConfiguration,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct JsonView {
+    key: Option<String>,
+    title: Option<String>,
+    software_system_id: Option<String>,
+    container_id: Option<String>,
+    elements: Vec<JsonViewElement>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct JsonViewElement {
+    id: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct JsonConfiguration {
+    styles: JsonStyles,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct JsonStyles {
+    elements: Vec<JsonElementStyle>,
+    relationships: Vec<JsonRelationshipStyle>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct JsonElementStyle {
+    tag: String,
+    background: Option<String>,
+    color: Option<String>,
+    shape: Option<String>,
+    stroke: Option<String>,
+    stroke_width: Option<u32>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct JsonRelationshipStyle {
+    tag: Option<String>,
+    thickness: Option<u32>,
+    color: Option<String>,
+    routing: Option<String>,
+    dashed: Option<bool>,
+}
+
+impl WorkspaceSerializer {
+    /// Builds a serializer from a Structurizr `workspace.json` document.
+    ///
+    /// Elements without a description reuse their name, since c4rs requires
+    /// one. Relationships and views referring to unknown element ids are
+    /// skipped.
+    pub fn from_workspace_json(json: &str) -> Result<Self, DslError> {
+        let workspace: JsonWorkspace = serde_json::from_str(json).map_err(invalid)?;
+
+        let mut serializer = WorkspaceSerializer::new();
+        if let Some(name) = &workspace.name {
+            serializer = serializer.name(name);
+        }
+        if let Some(description) = &workspace.description {
+            serializer = serializer.description(description);
+        }
+
+        let mut ids: HashMap<&str, ElementId> = HashMap::new();
+        let mut relationships: Vec<&JsonRelationship> = Vec::new();
+        for json_person in &workspace.model.people {
+            let person = Person::builder()
+                .name(json_person.name.clone())
+                .description(description_or_name(json_person))
+                .maybe_location(location(json_person))
+                .build()
+                .map_err(invalid)?;
+            ids.insert(&json_person.id, person.id().clone());
+            relationships.extend(&json_person.relationships);
+            serializer = serializer.add_person(&person);
+        }
+        for json_system in &workspace.model.software_systems {
+            let mut builder = SoftwareSystem::builder()
+                .name(json_system.name.clone())
+                .description(description_or_name(json_system))
+                .maybe_location(location(json_system));
+            for json_container in &json_system.containers {
+                let container = build_container(json_container, &mut ids, &mut relationships)?;
+                builder = builder.add_container(&container);
+            }
+            let system = builder.build().map_err(invalid)?;
+            ids.insert(&json_system.id, system.id().clone());
+            relationships.extend(&json_system.relationships);
+            serializer = serializer.add_software_system(&system);
+        }
+
+        // Relationships and views refer to elements by id, so identifiers are
+        // only resolved when serializing and stay correct if the caller keeps
+        // changing the returned serializer.
+        for rel in relationships {
+            if let (Some(source), Some(target)) = (
+                ids.get(rel.source_id.as_str()),
+                ids.get(rel.destination_id.as_str()),
+            ) {
+                serializer = serializer.add_relationship_by_id(
+                    source,
+                    target,
+                    rel.description.as_deref().unwrap_or_default(),
+                    rel.technology.as_deref(),
+                );
+            }
+        }
+
+        let views = &workspace.views;
+        let scoped_views = [
+            (ViewType::SystemLandscape, &views.system_landscape_views),
+            (ViewType::SystemContext, &views.system_context_views),
+            (ViewType::Container, &views.container_views),
+            (ViewType::Component, &views.component_views),
+        ];
+        for (view_type, json_views) in scoped_views {
+            for json_view in json_views {
+                let scope = json_view
+                    .software_system_id
+                    .as_ref()
+                    .or(json_view.container_id.as_ref())
+                    .and_then(|id| ids.get(id.as_str()));
+                if view_type.requires_element_identifier() && scope.is_none() {
+                    continue;
+                }
+                let title = json_view
+                    .title
+                    .clone()
+                    .or_else(|| json_view.key.clone())
+                    .unwrap_or_else(|| view_type.to_string());
+                let included = json_view
+                    .elements
+                    .iter()
+                    .filter_map(|e| ids.get(e.id.as_str()).cloned());
+                let view = ViewConfiguration::builder()
+                    .view_type(view_type)
+                    .element_identifier(scope.map(|s| s.as_str().to_string()).unwrap_or_default())
+                    .maybe_key(json_view.key.clone())
+                    .title(title)
+                    .build()
+                    .include_ids(included);
+                serializer = serializer.add_view(match scope {
+                    Some(id) => view.with_scope(id.clone()),
+                    None => view,
+                });
+            }
+        }
+
+        for style in &views.configuration.styles.elements {
+            serializer = serializer.add_element_style(
+                ElementStyle::builder()
+                    .identifier(style.tag.clone())
+                    .maybe_background(style.background.clone())
+                    .maybe_color(style.color.clone())
+                    .maybe_shape(style.shape.clone())
+                    .maybe_stroke(style.stroke.clone())
+                    .maybe_stroke_width(style.stroke_width.map(|w| w.to_string()))
+                    .build(),
+            );
+        }
+        for style in &views.configuration.styles.relationships {
+            serializer = serializer.add_relationship_style(
+                RelationshipStyle::builder()
+                    .maybe_identifier(style.tag.clone())
+                    .maybe_thickness(style.thickness.map(|t| t.to_string()))
+                    .maybe_color(style.color.clone())
+                    .maybe_router(style.routing.clone())
+                    .maybe_dashed(style.dashed)
+                    .build(),
+            );
+        }
+
+        Ok(serializer)
+    }
+}
+
+fn build_container<'a>(
+    json: &'a JsonElement,
+    ids: &mut HashMap<&'a str, ElementId>,
+    relationships: &mut Vec<&'a JsonRelationship>,
+) -> Result<Container, DslError> {
+    let mut builder = Container::builder()
+        .name(json.name.clone())
+        .description(description_or_name(json))
+        .container_type(container_type(json));
+    for technology in technologies(json) {
+        builder = builder.add_technology(technology);
+    }
+    for json_component in &json.components {
+        let mut component_builder = Component::builder()
+            .name(json_component.name.clone())
+            .description(description_or_name(json_component));
+        for technology in technologies(json_component) {
+            component_builder = component_builder.add_technology(technology);
+        }
+        let component = component_builder.build().map_err(invalid)?;
+        ids.insert(&json_component.id, component.id().clone());
+        relationships.extend(&json_component.relationships);
+        builder = builder.add_component(&component);
+    }
+    let container = builder.build().map_err(invalid)?;
+    ids.insert(&json.id, container.id().clone());
+    relationships.extend(&json.relationships);
+    Ok(container)
+}
+
+fn invalid(err: impl std::fmt::Display) -> DslError {
+    DslError::InvalidWorkspaceJson {
+        reason: err.to_string(),
+    }
+}
+
+fn description_or_name(json: &JsonElement) -> String {
+    json.description
+        .clone()
+        .filter(|d| !d.is_empty())
+        .unwrap_or_else(|| json.name.clone())
+}
+
+fn location(json: &JsonElement) -> Option<Location> {
+    match json.location.as_deref() {
+        Some("External") => Some(Location::External),
+        Some("Internal") => Some(Location::Internal),
+        _ => None,
+    }
+}
+
+fn technologies(json: &JsonElement) -> Vec<String> {
+    json.technology
+        .iter()
+        .flat_map(|t| t.split(','))
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Structurizr has no container type, so it is guessed from the tags.
+fn container_type(json: &JsonElement) -> ContainerType {
+    let tags = json.tags.as_deref().unwrap_or_default();
+    let has_tag = |tag: &str| tags.split(',').any(|t| t.trim() == tag);
+    let has_type_tag =
+        |container_type: &ContainerType| has_tag(&container_type.as_structurizr_tag());
+    if has_type_tag(&ContainerType::Database) {
+        ContainerType::Database
+    } else if has_tag("Web Browser") || has_type_tag(&ContainerType::WebApplication) {
+        ContainerType::WebApplication
+    } else if has_tag("Mobile App") {
+        ContainerType::MobileApplication
+    } else if has_type_tag(&ContainerType::MessageBus) || has_tag("Queue") {
+        ContainerType::MessageBus
+    } else {
+        ContainerType::Other("Container".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKSPACE: &str = r##"{
+        "name": "Banking",
+        "description": "Internet banking",
+        "model": {
+            "people": [{
+                "id": "1",
+                "name": "Customer",
+                "description": "A bank customer",
+                "location": "External",
+                "relationships": [
+                    { "id": "10", "sourceId": "1", "destinationId": "3", "description": "Uses" }
+                ]
+            }],
+            "softwareSystems": [{
+                "id": "2",
+                "name": "Internet Banking",
+                "containers": [{
+                    "id": "3",
+                    "name": "API",
+                    "description": "Serves JSON",
+                    "technology": "Rust, Axum",
+                    "relationships": [
+                        { "id": "11", "sourceId": "3", "destinationId": "4", "technology": "SQL" }
+                    ]
+                }, {
+                    "id": "4",
+                    "name": "Database",
+                    "description": "Stores accounts",
+                    "tags": "Element,Container,Database"
+                }]
+            }, {
+                "id": "5",
+                "name": "Mainframe",
+                "description": "Core banking",
+                "location": "External"
+            }]
+        },
+        "views": {
+            "containerViews": [{
+                "key": "Containers",
+                "softwareSystemId": "2",
+                "elements": [{ "id": "1" }, { "id": "3" }, { "id": "4" }]
+            }],
+            "configuration": {
+                "styles": {
+                    "elements": [{ "tag": "Database", "shape": "Cylinder", "strokeWidth": 2 }],
+                    "relationships": [{ "tag": "Relationship", "dashed": false }]
+                }
+            }
+        }
+    }"##;
+
+    #[test]
+    fn test_from_workspace_json() {
+        let dsl = WorkspaceSerializer::from_workspace_json(WORKSPACE)
+            .unwrap()
+            .serialize()
+            .unwrap();
+
+        assert!(dsl.starts_with(r#"workspace "Banking" "Internet banking" {"#));
+        assert!(dsl.contains(r#"c = person "Customer" "A bank customer""#));
+        assert!(dsl.contains(r#"ib = softwareSystem "Internet Banking" "Internet Banking" {"#));
+        assert!(dsl.contains(r#"a = container "API" "Serves JSON""#));
+        assert!(dsl.contains(r#"m = softwareSystem "Mainframe" "Core banking""#));
+        assert!(dsl.contains(r#"c -> ib.a "Uses""#));
+        assert!(dsl.contains(r#"ib.a -> ib.d "" "SQL""#));
+        assert!(dsl.contains(r#"container ib "Containers" {"#));
+        assert!(dsl.contains("include c\n            include ib.a\n            include ib.d\n"));
+        assert!(dsl.contains("shape Cylinder"));
+        assert!(dsl.contains("strokeWidth 2"));
+    }
+
+    #[test]
+    fn test_imported_references_survive_later_changes() {
+        let insider = Person::builder()
+            .name("Insider Buyer".into())
+            .description("Shares the system's identifier".into())
+            .build()
+            .unwrap();
+        let dsl = WorkspaceSerializer::from_workspace_json(WORKSPACE)
+            .unwrap()
+            .add_person(&insider)
+            .serialize()
+            .unwrap();
+
+        assert!(dsl.contains(r#"ib = person "Insider Buyer""#));
+        assert!(dsl.contains(r#"ib1 = softwareSystem "Internet Banking""#));
+        assert!(dsl.contains(r#"c -> ib1.a "Uses""#), "{dsl}");
+        assert!(dsl.contains(r#"ib1.a -> ib1.d "" "SQL""#));
+        assert!(dsl.contains(r#"container ib1 "Containers" {"#));
+        assert!(dsl.contains("include c\n            include ib1.a\n            include ib1.d\n"));
+    }
+
+    #[test]
+    fn test_from_workspace_json_invalid() {
+        let result = WorkspaceSerializer::from_workspace_json("{ not json");
+        assert!(matches!(result, Err(DslError::InvalidWorkspaceJson { .. })));
+    }
+}
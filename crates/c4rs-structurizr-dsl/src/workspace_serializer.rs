@@ -1,17 +1,29 @@
 use crate::{
-    StylesSerializer, ViewConfiguration, ViewsSerializer,
-    error::DslError,
+    StylesPosition, StylesSerializer, ViewConfiguration, ViewType, ViewsSerializer,
+    error::{DslError, DslWarning},
     styles::{ElementStyle, RelationshipStyle},
-    templates::helpers::escape_dsl_string,
+    templates::helpers::{escape_dsl_string, format_identifier},
     writer::{self, DslWriter},
 };
-use c4rs_core::c4::{Component, Container, Element, ElementId, Person, SoftwareSystem};
-use std::collections::{HashMap, HashSet};
+use c4rs_core::c4::{
+    CodeElement, Component, Container, ContainerType, Element, ElementId, ElementType,
+    InteractionStyle, Location, ModelRelationship, Person, Relationship, SoftwareSystem,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Version of the formatting produced by
+/// [`WorkspaceSerializer::serialize_stable`]. Indentation (four spaces per
+/// level), blank-line placement, and the order of sections and fields are
+/// fixed for a given version and only change with a major crate release.
+pub const STABLE_FORMAT_VERSION: u32 = 1;
+
+/// Identifier length above which [`DslWarning::LongIdentifier`] is reported,
+/// unless changed with [`WorkspaceSerializer::max_identifier_length`].
+pub const DEFAULT_MAX_IDENTIFIER_LENGTH: usize = 255;
 
 #[derive(Debug)]
 pub struct WorkspaceSerializer {
     writer: DslWriter,
-    used_identifiers: HashSet<String>,
     /// Maps an element's ElementId to its resolved hierarchical DSL path.
     id_to_path: HashMap<ElementId, String>,
     persons: Vec<Person>,
@@ -21,14 +33,102 @@ pub struct WorkspaceSerializer {
     styles_serializer: StylesSerializer,
     name: Option<String>,
     description: Option<String>,
+    workspace_id: Option<i64>,
+    extends: Option<String>,
+    /// Workspace properties, sorted by key so output is deterministic.
+    properties: BTreeMap<String, String>,
+    comments: Vec<String>,
+    enterprise_boundary: Option<String>,
+    identifier_mode: IdentifierMode,
+    identifier_header: IdentifierHeader,
+    /// Raw `!identifiers` value overriding `identifier_mode` in the header.
+    identifier_directive: Option<String>,
+    inferred_descriptions: bool,
+    inline_relationships: bool,
+    require_nonempty: bool,
+    max_identifier_length: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct StoredRelationship {
-    source_id: ElementId,
-    target_id: ElementId,
+    source: RelationshipEndpoint,
+    target: RelationshipEndpoint,
     description: String,
     technology: Option<String>,
+    url: Option<String>,
+    tags: Vec<String>,
+}
+
+impl StoredRelationship {
+    fn new(
+        source: RelationshipEndpoint,
+        target: RelationshipEndpoint,
+        description: &str,
+        technology: Option<&str>,
+    ) -> Self {
+        Self {
+            source,
+            target,
+            description: description.to_string(),
+            technology: technology.map(str::to_string),
+            url: None,
+            tags: Vec::new(),
+        }
+    }
+}
+
+/// One side of a stored relationship, either a concrete element, an
+/// element that must be part of the model, an element name, or a literal
+/// DSL identifier. All but the last are resolved to DSL paths at serialize
+/// time.
+#[derive(Debug, Clone)]
+enum RelationshipEndpoint {
+    Id(ElementId),
+    Registered(ElementId),
+    Name(String),
+    Identifier(String),
+}
+
+/// Identifier strategy declared via `!identifiers` in the workspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdentifierMode {
+    /// Every identifier is global; nested elements are referenced by their
+    /// own identifier only.
+    Flat,
+    /// Nested elements are referenced by dot-separated paths such as
+    /// `system.container`.
+    #[default]
+    Hierarchical,
+}
+
+impl IdentifierMode {
+    /// Returns the DSL reference for a child element nested under `parent_path`.
+    fn child_path(self, parent_path: &str, identifier: &str) -> String {
+        match self {
+            IdentifierMode::Flat => identifier.to_string(),
+            IdentifierMode::Hierarchical => format!("{}.{}", parent_path, identifier),
+        }
+    }
+}
+
+impl std::fmt::Display for IdentifierMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdentifierMode::Flat => write!(f, "flat"),
+            IdentifierMode::Hierarchical => write!(f, "hierarchical"),
+        }
+    }
+}
+
+/// When the `!identifiers` directive is written to the workspace header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdentifierHeader {
+    /// Only when the model has nested elements (containers or components),
+    /// the only case where the identifier mode changes how they are referenced.
+    Auto,
+    #[default]
+    Always,
+    Never,
 }
 
 impl Default for WorkspaceSerializer {
@@ -41,7 +141,6 @@ impl WorkspaceSerializer {
     pub fn new() -> Self {
         Self {
             writer: DslWriter::new(),
-            used_identifiers: HashSet::new(),
             id_to_path: HashMap::new(),
             persons: Vec::new(),
             software_systems: Vec::new(),
@@ -50,29 +149,210 @@ impl WorkspaceSerializer {
             styles_serializer: StylesSerializer::new(),
             name: None,
             description: None,
+            workspace_id: None,
+            extends: None,
+            properties: BTreeMap::new(),
+            comments: Vec::new(),
+            enterprise_boundary: None,
+            identifier_mode: IdentifierMode::default(),
+            identifier_header: IdentifierHeader::default(),
+            identifier_directive: None,
+            inferred_descriptions: false,
+            inline_relationships: false,
+            require_nonempty: false,
+            max_identifier_length: DEFAULT_MAX_IDENTIFIER_LENGTH,
+        }
+    }
+
+    /// Creates a serializer with room for `systems` software systems and
+    /// `relationships` relationships, avoiding reallocation when the counts
+    /// are known upfront. Output is the same as with [`new`](Self::new).
+    pub fn with_capacity_hints(systems: usize, relationships: usize) -> Self {
+        Self {
+            id_to_path: HashMap::with_capacity(systems),
+            software_systems: Vec::with_capacity(systems),
+            relationships: Vec::with_capacity(relationships),
+            ..Self::new()
         }
     }
 
-    pub fn name(mut self, name: &str) -> Self {
-        self.name = Some(name.to_string());
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
         self
     }
 
-    pub fn description(mut self, description: &str) -> Self {
-        self.description = Some(description.to_string());
+    /// Sets the Structurizr cloud or on-premises workspace id, emitted as the
+    /// `structurizr.workspaceId` workspace property.
+    pub fn workspace_id(mut self, id: i64) -> Self {
+        self.workspace_id = Some(id);
         self
     }
 
-    pub fn add_person(mut self, person: &Person) -> Self {
-        self.persons.push(person.clone());
+    /// Makes the output extend a base workspace, written as
+    /// `workspace extends "path" ...`, so it can layer onto a shared model.
+    pub fn extends(mut self, path: &str) -> Self {
+        self.extends = Some(path.to_string());
         self
     }
 
-    pub fn add_software_system(mut self, system: &SoftwareSystem) -> Self {
-        self.software_systems.push(system.clone());
+    /// Adds a workspace property, replacing any existing value for `key`.
+    /// Properties are written in key order regardless of insertion order.
+    pub fn add_property(mut self, key: &str, value: &str) -> Self {
+        self.properties.insert(key.to_string(), value.to_string());
         self
     }
 
+    /// Adds a boolean workspace property, written as `"true"` or `"false"`.
+    pub fn add_property_bool(self, key: &str, value: bool) -> Self {
+        self.add_property(key, &value.to_string())
+    }
+
+    /// Adds a numeric workspace property. Whole numbers are written without
+    /// a fractional part, so `3.0` becomes `"3"`.
+    pub fn add_property_number(self, key: &str, value: f64) -> Self {
+        self.add_property(key, &value.to_string())
+    }
+
+    pub fn identifier_mode(mut self, mode: IdentifierMode) -> Self {
+        self.identifier_mode = mode;
+        self
+    }
+
+    pub fn identifier_header(mut self, header: IdentifierHeader) -> Self {
+        self.identifier_header = header;
+        self
+    }
+
+    /// Writes `value` verbatim as the `!identifiers` directive instead of
+    /// the [`IdentifierMode`], e.g. for strategies this crate does not know
+    /// yet. References are still resolved using the identifier mode.
+    /// Serialization fails unless `value` is a single token.
+    pub fn identifier_directive(mut self, value: &str) -> Self {
+        self.identifier_directive = Some(value.to_string());
+        self
+    }
+
+    pub fn styles_position(mut self, position: StylesPosition) -> Self {
+        self.views_serializer.set_styles_position(position);
+        self
+    }
+
+    /// Marks the view with the given key (see [`ViewConfiguration::dsl_key`])
+    /// as the one shown first. Serialization fails if no such view exists.
+    pub fn default_view(mut self, key: &str) -> Self {
+        self.views_serializer.set_default_view(key.to_string());
+        self
+    }
+
+    /// Emits `include *` for views that specify no include expressions.
+    /// Enabled by default.
+    pub fn auto_include(mut self, auto_include: bool) -> Self {
+        self.views_serializer.set_auto_include(auto_include);
+        self
+    }
+
+    /// Wraps all internal top-level persons and software systems in a named
+    /// group that acts as the enterprise boundary. External elements are
+    /// written after the group.
+    pub fn enterprise_boundary(mut self, name: &str) -> Self {
+        self.enterprise_boundary = Some(name.to_string());
+        self
+    }
+
+    /// Controls the blank lines separating workspace sections. Defaults to
+    /// true.
+    pub fn blank_lines(mut self, blank_lines: bool) -> Self {
+        self.writer.set_blank_lines(blank_lines);
+        self
+    }
+
+    /// Enables compact output with no indentation or blank separator lines.
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.writer.set_compact(compact);
+        self
+    }
+
+    /// Fills in empty relationship descriptions from the endpoint types:
+    /// `Reads from and writes to` for database targets, `Uses` for persons,
+    /// and `Depends on` otherwise. Defaults to false.
+    pub fn inferred_descriptions(mut self, inferred: bool) -> Self {
+        self.inferred_descriptions = inferred;
+        self
+    }
+
+    /// Writes each relationship inside its source element's block as
+    /// `this -> target` instead of in the flat list after the elements.
+    /// Relationships whose source is not a model element stay in the list.
+    /// Defaults to false.
+    pub fn inline_relationships(mut self, inline: bool) -> Self {
+        self.inline_relationships = inline;
+        self
+    }
+
+    /// Makes serialization fail with [`DslError::EmptyModel`] when no
+    /// persons or software systems were added. Defaults to false.
+    pub fn require_nonempty(mut self, require: bool) -> Self {
+        self.require_nonempty = require;
+        self
+    }
+
+    /// Sets the resolved identifier length above which
+    /// [`DslWarning::LongIdentifier`] is reported. Defaults to
+    /// [`DEFAULT_MAX_IDENTIFIER_LENGTH`].
+    pub fn max_identifier_length(mut self, length: usize) -> Self {
+        self.max_identifier_length = length;
+        self
+    }
+
+    /// Adds a comment written at the top of the output, before the
+    /// `workspace` block.
+    pub fn add_comment(mut self, comment: &str) -> Self {
+        self.comments.push(comment.to_string());
+        self
+    }
+
+    pub fn add_person(self, person: &Person) -> Self {
+        self.add_person_owned(person.clone())
+    }
+
+    /// Like [`add_person`](Self::add_person), but takes ownership instead of
+    /// cloning.
+    pub fn add_person_owned(mut self, mut person: Person) -> Self {
+        self.add_recorded_relationships(person.drain_relationships());
+        self.persons.push(person);
+        self
+    }
+
+    pub fn add_software_system(self, system: &SoftwareSystem) -> Self {
+        self.add_software_system_owned(system.clone())
+    }
+
+    /// Like [`add_software_system`](Self::add_software_system), but takes
+    /// ownership instead of cloning the system and its containers.
+    pub fn add_software_system_owned(mut self, mut system: SoftwareSystem) -> Self {
+        self.add_recorded_relationships(system.drain_relationships());
+        self.software_systems.push(system);
+        self
+    }
+
+    /// Collects relationships recorded on an element with its `uses`
+    /// method, e.g. [`Person::uses`].
+    fn add_recorded_relationships(&mut self, recorded: Vec<ModelRelationship>) {
+        for rel in recorded {
+            self.relationships.push(StoredRelationship::new(
+                RelationshipEndpoint::Id(rel.source().clone()),
+                RelationshipEndpoint::Id(rel.target().clone()),
+                rel.description(),
+                rel.technology(),
+            ));
+        }
+    }
+
     pub fn add_relationship(
         mut self,
         source: &impl Element,
@@ -80,12 +360,112 @@ impl WorkspaceSerializer {
         description: &str,
         technology: Option<&str>,
     ) -> Self {
-        self.relationships.push(StoredRelationship {
-            source_id: source.id().clone(),
-            target_id: target.id().clone(),
-            description: description.to_string(),
-            technology: technology.map(|s| s.to_string()),
-        });
+        self.relationships.push(StoredRelationship::new(
+            RelationshipEndpoint::Id(source.id().clone()),
+            RelationshipEndpoint::Id(target.id().clone()),
+            description,
+            technology,
+        ));
+        self
+    }
+
+    /// Adds a core [`Relationship`], keeping its url and tags. Asynchronous
+    /// relationships are also tagged `Asynchronous`.
+    pub fn add_typed_relationship<S: Element, T: Element>(
+        mut self,
+        relationship: &Relationship<S, T>,
+    ) -> Self {
+        let mut stored = StoredRelationship::new(
+            RelationshipEndpoint::Id(relationship.source().id().clone()),
+            RelationshipEndpoint::Id(relationship.target().id().clone()),
+            relationship.description(),
+            relationship.technology(),
+        );
+        stored.url = relationship.url().map(str::to_string);
+        if relationship.interaction_style() == InteractionStyle::Asynchronous {
+            stored.tags.push(InteractionStyle::Asynchronous.to_string());
+        }
+        for tag in relationship.tags() {
+            if !stored.tags.contains(tag) {
+                stored.tags.push(tag.clone());
+            }
+        }
+        self.relationships.push(stored);
+        self
+    }
+
+    /// Relates two elements of the model, however deeply nested, by their
+    /// resolved DSL paths (e.g. `s.a.h` for a component). Unlike
+    /// [`add_relationship`], serialization fails if either element was never
+    /// added to the model, or is a code element, which has no DSL identifier.
+    ///
+    /// [`add_relationship`]: Self::add_relationship
+    pub fn relate(mut self, from: &impl Element, to: &impl Element, description: &str) -> Self {
+        self.relationships.push(StoredRelationship::new(
+            RelationshipEndpoint::Registered(from.id().clone()),
+            RelationshipEndpoint::Registered(to.id().clone()),
+            description,
+            None,
+        ));
+        self
+    }
+
+    /// Adds a relationship whose endpoints are looked up by element name
+    /// when the workspace is serialized. Serialization fails if a name
+    /// matches no element or more than one element.
+    pub fn add_relationship_by_name(
+        mut self,
+        source_name: &str,
+        target_name: &str,
+        description: &str,
+        technology: Option<&str>,
+    ) -> Self {
+        self.relationships.push(StoredRelationship::new(
+            RelationshipEndpoint::Name(source_name.to_string()),
+            RelationshipEndpoint::Name(target_name.to_string()),
+            description,
+            technology,
+        ));
+        self
+    }
+
+    /// Adds a relationship between two literal DSL identifiers, written as-is.
+    /// Under [`IdentifierMode::Flat`], dotted identifiers are rejected at
+    /// serialize time.
+    pub fn add_relationship_by_identifier(
+        mut self,
+        source_identifier: &str,
+        target_identifier: &str,
+        description: &str,
+        technology: Option<&str>,
+    ) -> Self {
+        self.relationships.push(StoredRelationship::new(
+            RelationshipEndpoint::Identifier(source_identifier.to_string()),
+            RelationshipEndpoint::Identifier(target_identifier.to_string()),
+            description,
+            technology,
+        ));
+        self
+    }
+
+    /// Adds a relationship between two elements by id. The endpoints are
+    /// resolved to DSL paths when serializing, like [`add_relationship`].
+    ///
+    /// [`add_relationship`]: Self::add_relationship
+    #[cfg(feature = "json")]
+    pub(crate) fn add_relationship_by_id(
+        mut self,
+        source: &ElementId,
+        target: &ElementId,
+        description: &str,
+        technology: Option<&str>,
+    ) -> Self {
+        self.relationships.push(StoredRelationship::new(
+            RelationshipEndpoint::Id(source.clone()),
+            RelationshipEndpoint::Id(target.clone()),
+            description,
+            technology,
+        ));
         self
     }
 
@@ -94,6 +474,12 @@ impl WorkspaceSerializer {
         self
     }
 
+    /// Adds an external theme URL, written inside the `views` block.
+    pub fn add_theme(mut self, url: &str) -> Self {
+        self.views_serializer.add_theme(url.to_string());
+        self
+    }
+
     pub fn add_element_style(mut self, style: ElementStyle) -> Self {
         self.styles_serializer = self.styles_serializer.add_element_style(style);
         self
@@ -114,160 +500,1064 @@ impl WorkspaceSerializer {
     }
 
     pub fn serialize(mut self) -> Result<String, DslError> {
-        let styles_dsl = self.styles_serializer.serialize()?;
+        let styles_dsl = self
+            .styles_serializer
+            .serialize()
+            .map_err(|e| self.section_error("styles", e.into()))?;
         if !styles_dsl.is_empty() {
             self.views_serializer
                 .set_styles_output(styles_dsl.to_string());
         }
 
-        self.writer.clear();
-        self.used_identifiers.clear();
-        self.id_to_path.clear();
+        if self.require_nonempty && self.persons.is_empty() && self.software_systems.is_empty() {
+            return Err(DslError::EmptyModel);
+        }
+        let compact = self.writer.is_compact();
+        let blank_lines = self.writer.has_blank_lines();
+        self.writer = DslWriter::with_capacity(self.estimated_capacity());
+        self.writer.set_compact(compact);
+        self.writer.set_blank_lines(blank_lines);
+        self.id_to_path = self.assign_paths()?;
+        self.resolve_view_scopes();
+        self.validate_view_hierarchy(&self.id_to_path)?;
+        self.validate_tags()?;
         self.write_workspace_header()?;
-        self.write_model_section()?;
+        self.write_model_section()
+            .map_err(|e| self.section_error("model", e))?;
         self.writer.unindent();
         self.writer.add_line("}");
 
-        self.write_views_section()?;
+        self.write_views_section()
+            .map_err(|e| self.section_error("views", e))?;
         self.writer.unindent();
         self.writer.add_line("}");
-        Ok(self.writer.as_output())
+        let output = self.writer.into_output();
+        #[cfg(debug_assertions)]
+        writer::validate_dsl_syntax(&output)?;
+        Ok(output)
     }
 
+    /// Serializes the workspace and returns a 64-bit FNV-1a hash of the
+    /// output. The hash depends only on the output bytes, so it is stable
+    /// across runs and platforms and can be committed for change detection.
+    pub fn content_hash(self) -> Result<u64, DslError> {
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0000_0100_0000_01b3;
+        let output = self.serialize()?;
+        Ok(output.bytes().fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        }))
+    }
+
+    /// Attaches the section being written and the current output line to
+    /// rendering failures. Errors that already name the offending element or
+    /// view are returned unchanged.
+    fn section_error(&self, section: &str, err: DslError) -> DslError {
+        match err {
+            DslError::TemplateError(_) | DslError::SerializationError(_) => DslError::InSection {
+                section: section.to_string(),
+                line: self.writer.line_count(),
+                source: Box::new(err),
+            },
+            other => other,
+        }
+    }
+
+    /// Number of persons, software systems, containers, components, and code
+    /// elements in the model.
+    pub fn count_elements(&self) -> usize {
+        self.model_elements().len()
+    }
+
+    pub fn count_relationships(&self) -> usize {
+        self.relationships.len()
+    }
+
+    /// Estimates the number of output lines from the element, relationship,
+    /// view, and style counts. Used to pre-allocate the writer.
+    pub fn estimated_capacity(&self) -> usize {
+        const HEADER_LINES: usize = 8;
+        const STYLE_LINES: usize = 6;
+        let systems: usize = self
+            .software_systems
+            .iter()
+            .map(|system| {
+                2 + system
+                    .containers()
+                    .iter()
+                    .map(|container| 2 + container.components().len())
+                    .sum::<usize>()
+            })
+            .sum();
+        let views: usize = self
+            .views_serializer
+            .views()
+            .iter()
+            .map(|v| 2 + v.include_elements.len() + v.exclude_elements.len())
+            .sum();
+        let styles = self.styles_serializer.element_styles().len()
+            + self.styles_serializer.relationship_styles().len();
+        HEADER_LINES
+            + self.persons.len()
+            + systems
+            + self.relationships.len()
+            + views
+            + styles * STYLE_LINES
+    }
+
+    /// Serializes the workspace and returns the output together with any
+    /// non-fatal diagnostics found along the way, such as views scoped to
+    /// unknown elements or components whose technologies share nothing with
+    /// their container's.
+    pub fn serialize_with_warnings(self) -> Result<(String, Vec<DslWarning>), DslError> {
+        let warnings = self.collect_warnings(&self.assign_paths()?);
+        Ok((self.serialize()?, warnings))
+    }
+
+    /// Runs every non-fatal check without serializing: views scoped to
+    /// unknown elements, component/container technology mismatches,
+    /// over-long identifiers, and relationship cycles. Fails if identifiers
+    /// cannot be resolved, e.g. two elements pinned to the same identifier.
+    pub fn warnings(&self) -> Result<Vec<DslWarning>, DslError> {
+        Ok(self.collect_warnings(&self.assign_paths()?))
+    }
+
+    /// Resolves every element's DSL identifier so they can be listed with
+    /// [`iter_resolved`](Self::iter_resolved) before serializing.
+    pub fn build_paths(&mut self) -> Result<(), DslError> {
+        self.id_to_path = self.assign_paths()?;
+        Ok(())
+    }
+
+    /// Yields each element's type, name, and resolved DSL identifier in
+    /// model order, e.g. `(Container, "API", "s.a")`. Empty until
+    /// [`build_paths`](Self::build_paths) has run.
+    pub fn iter_resolved(&self) -> impl Iterator<Item = (ElementType, String, String)> {
+        self.model_elements().into_iter().filter_map(|element| {
+            let path = self.id_to_path.get(element.id())?;
+            Some((
+                element.element_type(),
+                element.name().to_string(),
+                path.clone(),
+            ))
+        })
+    }
+
+    /// Checks the model for mistakes that would produce invalid DSL without
+    /// serializing it.
+    pub fn validate(&self) -> Result<(), DslError> {
+        let paths = self.assign_paths()?;
+        for rel in &self.relationships {
+            self.resolve_endpoint(&paths, &rel.source)?;
+            self.resolve_endpoint(&paths, &rel.target)?;
+        }
+        self.validate_tags()?;
+        self.validate_view_hierarchy(&paths)
+    }
+
+    /// Rejects tags containing a comma. Structurizr splits tag values on
+    /// commas and has no way to escape them, so such a tag would silently
+    /// become several.
+    fn validate_tags(&self) -> Result<(), DslError> {
+        let element_tags = self.model_elements().into_iter().flat_map(|e| e.tags());
+        let relationship_tags = self.relationships.iter().flat_map(|r| &r.tags);
+        let view_tags = self.views_serializer.views().iter().flat_map(|v| &v.tags);
+        match element_tags
+            .chain(relationship_tags)
+            .chain(view_tags)
+            .find(|tag| tag.contains(','))
+        {
+            Some(tag) => Err(DslError::InvalidTag { tag: tag.clone() }),
+            None => Ok(()),
+        }
+    }
+
+    /// Ensures scoped views point at the right kind of element: container
+    /// views at a software system, component views at a container. Scopes
+    /// that are not elements of the model are left to
+    /// [`DslWarning::ViewScopeNotFound`].
+    fn validate_view_hierarchy(&self, paths: &HashMap<ElementId, String>) -> Result<(), DslError> {
+        let types: HashMap<&str, ElementType> = self
+            .model_elements()
+            .into_iter()
+            .filter_map(|e| Some((paths.get(e.id())?.as_str(), e.element_type())))
+            .collect();
+        for view in self.views_serializer.views() {
+            let expected = match view.view_type {
+                ViewType::Container => ElementType::SoftwareSystem,
+                ViewType::Component => ElementType::Container,
+                _ => continue,
+            };
+            if let Some(actual) = types.get(Self::scope_of(view, paths).as_str())
+                && *actual != expected
+            {
+                return Err(DslError::InvalidViewScope {
+                    view: view.dsl_key(),
+                    expected: expected.to_string(),
+                    actual: actual.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the identifier a view is scoped to, resolving views created
+    /// with [`ViewConfiguration::default_for`] through `paths`.
+    fn scope_of(view: &ViewConfiguration, paths: &HashMap<ElementId, String>) -> String {
+        match view.scope() {
+            Some(id) => Self::resolve_path(paths, id),
+            None => view.element_identifier.clone(),
+        }
+    }
+
+    /// Points views created with [`ViewConfiguration::default_for`], or
+    /// imported from JSON, at their elements' resolved DSL identifiers.
+    fn resolve_view_scopes(&mut self) {
+        let paths = &self.id_to_path;
+        for view in self.views_serializer.views_mut() {
+            view.resolve_references(|id| Self::resolve_path(paths, id));
+        }
+    }
+
+    fn collect_warnings(&self, paths: &HashMap<ElementId, String>) -> Vec<DslWarning> {
+        let known: HashSet<&str> = paths.values().map(String::as_str).collect();
+        let mut warnings = Vec::new();
+        for element in self.model_elements() {
+            if let Some(path) = paths.get(element.id())
+                && path.len() > self.max_identifier_length
+            {
+                warnings.push(DslWarning::LongIdentifier {
+                    id: path.clone(),
+                    length: path.len(),
+                });
+            }
+        }
+        for view in self.views_serializer.views() {
+            let scoped = matches!(
+                view.view_type,
+                ViewType::SystemContext
+                    | ViewType::Container
+                    | ViewType::Component
+                    | ViewType::Dynamic
+                    | ViewType::Deployment
+            );
+            if view.dsl_identifier().is_none() || !scoped {
+                continue;
+            }
+            let identifier = Self::scope_of(view, paths);
+            if identifier != "*" && !known.contains(identifier.as_str()) {
+                warnings.push(DslWarning::ViewScopeNotFound {
+                    view: view.dsl_key(),
+                    identifier,
+                });
+            }
+        }
+        for system in &self.software_systems {
+            for container in system.containers() {
+                for component in container.components() {
+                    let shares_technology = component
+                        .technologies()
+                        .iter()
+                        .any(|t| container.technologies().contains(t));
+                    if !container.technologies().is_empty()
+                        && !component.technologies().is_empty()
+                        && !shares_technology
+                    {
+                        warnings.push(DslWarning::TechnologyMismatch {
+                            container: container.name().to_string(),
+                            component: component.name().to_string(),
+                        });
+                    }
+                }
+            }
+        }
+        warnings.extend(
+            self.relationship_cycles(paths)
+                .into_iter()
+                .map(|cycle| DslWarning::RelationshipCycle { cycle }),
+        );
+        warnings
+    }
+
+    /// Finds cycles in the relationship graph such as `a -> b -> c -> a`,
+    /// each reported as the DSL paths along the cycle. Cycles are legal in
+    /// C4 but often unintended, so they are diagnostics rather than errors
+    /// and are also included in [`warnings`](Self::warnings). Relationships
+    /// whose endpoints cannot be resolved are skipped, and nothing is found
+    /// if identifiers cannot be assigned at all; [`validate`](Self::validate)
+    /// reports both.
+    pub fn detect_cycles(&self) -> Vec<Vec<String>> {
+        match self.assign_paths() {
+            Ok(paths) => self.relationship_cycles(&paths),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Lists every elementary cycle once, starting from the node that
+    /// appears first in the relationships.
+    fn relationship_cycles(&self, paths: &HashMap<ElementId, String>) -> Vec<Vec<String>> {
+        let mut nodes: Vec<String> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut edges: Vec<Vec<usize>> = Vec::new();
+        let mut node = |path: String, edges: &mut Vec<Vec<usize>>| {
+            *index.entry(path.clone()).or_insert_with(|| {
+                nodes.push(path);
+                edges.push(Vec::new());
+                nodes.len() - 1
+            })
+        };
+        for rel in &self.relationships {
+            let (Ok(source), Ok(target)) = (
+                self.resolve_endpoint(paths, &rel.source),
+                self.resolve_endpoint(paths, &rel.target),
+            ) else {
+                continue;
+            };
+            let source = node(source, &mut edges);
+            let target = node(target, &mut edges);
+            if !edges[source].contains(&target) {
+                edges[source].push(target);
+            }
+        }
+
+        let mut stack = Vec::new();
+        let mut cycles = Vec::new();
+        for start in 0..edges.len() {
+            Self::find_cycles(start, start, &edges, &mut stack, &mut cycles);
+        }
+        cycles
+            .into_iter()
+            .map(|cycle| cycle.into_iter().map(|i| nodes[i].clone()).collect())
+            .collect()
+    }
+
+    /// Depth-first search for the cycles through `start` that visit no node
+    /// earlier than it, so each cycle is found from exactly one start node.
+    fn find_cycles(
+        start: usize,
+        node: usize,
+        edges: &[Vec<usize>],
+        stack: &mut Vec<usize>,
+        cycles: &mut Vec<Vec<usize>>,
+    ) {
+        stack.push(node);
+        for &next in &edges[node] {
+            if next == start {
+                cycles.push(stack.clone());
+            } else if next > start && !stack.contains(&next) {
+                Self::find_cycles(start, next, edges, stack, cycles);
+            }
+        }
+        stack.pop();
+    }
+
+    /// Serializes the full model but only the views with the given keys.
+    pub fn serialize_views(mut self, keys: &[&str]) -> Result<String, DslError> {
+        self.views_serializer.retain_views(keys)?;
+        self.serialize()
+    }
+
+    /// Splits the model into one workspace per software system, named after
+    /// the system. Each keeps every person, its system's relationships, and
+    /// the other systems those relationships reach, without their containers
+    /// (out of scope). Relationships into another system's containers, or
+    /// given by literal identifier, are dropped. Header settings, comments,
+    /// styles, and themes are copied; views are not, since they reference
+    /// elements that may no longer exist.
+    pub fn split_by_system(&self) -> Vec<(String, WorkspaceSerializer)> {
+        let person_ids: HashSet<&ElementId> = self.persons.iter().map(|p| p.id()).collect();
+        self.software_systems
+            .iter()
+            .map(|system| {
+                let mut local: HashSet<&ElementId> = HashSet::from([system.id()]);
+                for container in system.containers() {
+                    local.insert(container.id());
+                    for component in container.components() {
+                        local.insert(component.id());
+                        local.extend(component.code_elements().iter().map(|c| c.id()));
+                    }
+                }
+                let others: Vec<&SoftwareSystem> = self
+                    .software_systems
+                    .iter()
+                    .filter(|s| s.id() != system.id())
+                    .collect();
+                let is_other = |id: &ElementId| others.iter().any(|s| s.id() == id);
+
+                let relationships: Vec<&StoredRelationship> = self
+                    .relationships
+                    .iter()
+                    .filter(|rel| {
+                        let (Some(source), Some(target)) =
+                            (self.endpoint_id(&rel.source), self.endpoint_id(&rel.target))
+                        else {
+                            return false;
+                        };
+                        let known =
+                            |id| local.contains(id) || person_ids.contains(id) || is_other(id);
+                        let between_persons =
+                            person_ids.contains(source) && person_ids.contains(target);
+                        known(source)
+                            && known(target)
+                            && (local.contains(source) || local.contains(target) || between_persons)
+                    })
+                    .collect();
+                let referenced = |other: &SoftwareSystem| {
+                    relationships.iter().any(|rel| {
+                        self.endpoint_id(&rel.source) == Some(other.id())
+                            || self.endpoint_id(&rel.target) == Some(other.id())
+                    })
+                };
+
+                let mut split = self.with_settings_of();
+                split.name = Some(system.name().to_string());
+                split.description = Some(system.description().to_string());
+                split.persons = self.persons.clone();
+                split.software_systems.push(system.clone());
+                for other in others.into_iter().filter(|other| referenced(other)) {
+                    split.software_systems.push(other.without_containers());
+                }
+                split.relationships = relationships.into_iter().cloned().collect();
+                (system.name().to_string(), split)
+            })
+            .collect()
+    }
+
+    /// Returns an empty serializer with the same output settings, comments,
+    /// styles, and themes as this one.
+    fn with_settings_of(&self) -> Self {
+        let mut copy = Self::new()
+            .blank_lines(self.writer.has_blank_lines())
+            .compact(self.writer.is_compact());
+        copy.extends = self.extends.clone();
+        copy.properties = self.properties.clone();
+        copy.comments = self.comments.clone();
+        copy.enterprise_boundary = self.enterprise_boundary.clone();
+        copy.identifier_mode = self.identifier_mode;
+        copy.identifier_header = self.identifier_header;
+        copy.identifier_directive = self.identifier_directive.clone();
+        copy.inferred_descriptions = self.inferred_descriptions;
+        copy.inline_relationships = self.inline_relationships;
+        copy.require_nonempty = self.require_nonempty;
+        copy.max_identifier_length = self.max_identifier_length;
+        copy.styles_serializer = self.styles_serializer.clone();
+        for theme in self.views_serializer.themes() {
+            copy.views_serializer.add_theme(theme.clone());
+        }
+        copy
+    }
+
+    /// Serializes the full model but only the views tagged with `tag`.
+    pub fn serialize_views_by_tag(mut self, tag: &str) -> Result<String, DslError> {
+        self.views_serializer.retain_views_tagged(tag);
+        self.serialize()
+    }
+
+    /// Serializes with the pinned formatting described by
+    /// [`STABLE_FORMAT_VERSION`], ignoring writer options such as compact
+    /// output.
+    pub fn serialize_stable(mut self) -> Result<String, DslError> {
+        self.writer.set_compact(false);
+        self.writer.set_blank_lines(true);
+        self.serialize()
+    }
+
+    /// Writes `workspace {` when neither name nor description is set. A
+    /// description without a name needs a positional name, so Structurizr's
+    /// default `Workspace` is used.
     fn write_workspace_header(&mut self) -> Result<(), DslError> {
-        let name = escape_dsl_string(self.name.as_deref().unwrap_or("Name"));
-        let description = escape_dsl_string(self.description.as_deref().unwrap_or("Description"));
+        let arguments = match (&self.name, &self.description) {
+            (None, None) => String::new(),
+            (Some(name), None) => format!(r#" "{}""#, escape_dsl_string(name)),
+            (name, Some(description)) => format!(
+                r#" "{}" "{}""#,
+                escape_dsl_string(name.as_deref().unwrap_or("Workspace")),
+                escape_dsl_string(description)
+            ),
+        };
+        for comment in &self.comments {
+            self.writer.add_comment(comment);
+        }
+        let keyword = match &self.extends {
+            Some(path) => format!(r#"workspace extends "{}""#, escape_dsl_string(path)),
+            None => "workspace".to_string(),
+        };
         self.writer
-            .add_line(&format!(r#"workspace "{}" "{}" {{"#, name, description));
+            .add_line(&format!("{}{} {{", keyword, arguments));
         self.writer.indent();
-        self.writer.add_line("!identifiers hierarchical");
-        self.writer.add_empty_line();
+        let has_nested = self
+            .software_systems
+            .iter()
+            .any(|s| !s.containers().is_empty());
+        let write_directive = match self.identifier_header {
+            IdentifierHeader::Always => true,
+            IdentifierHeader::Auto => has_nested,
+            IdentifierHeader::Never => false,
+        };
+        if write_directive {
+            let directive = match &self.identifier_directive {
+                Some(value) => {
+                    if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '"') {
+                        return Err(DslError::InvalidIdentifierDirective {
+                            value: value.clone(),
+                        });
+                    }
+                    value.clone()
+                }
+                None => self.identifier_mode.to_string(),
+            };
+            self.writer.add_line(&format!("!identifiers {}", directive));
+        }
+        let mut properties = self.properties.clone();
+        if let Some(id) = self.workspace_id {
+            properties.insert("structurizr.workspaceId".to_string(), id.to_string());
+        }
+        if !properties.is_empty() {
+            self.writer.write_block("properties", |w| {
+                for (key, value) in &properties {
+                    w.add_line(&format!(
+                        r#""{}" "{}""#,
+                        escape_dsl_string(key),
+                        escape_dsl_string(value)
+                    ));
+                }
+            });
+        }
+        if write_directive || !properties.is_empty() {
+            self.writer.add_empty_line();
+        }
         self.writer.add_line("model {");
         self.writer.indent();
         Ok(())
     }
 
     fn resolve_identifier(element_id: &ElementId, used: &mut HashSet<String>) -> String {
-        let base = element_id.as_str().to_string();
-        let mut identifier = base.clone();
-        let mut counter = 1;
-        while used.contains(&identifier) {
-            identifier = format!("{}{}", base, counter);
-            counter += 1;
-        }
-        used.insert(identifier.clone());
+        let identifier = if element_id.is_explicit() {
+            element_id.as_str().to_string()
+        } else {
+            let base = format_identifier(element_id.as_str());
+            let mut identifier = base.clone();
+            let mut counter = 1;
+            while used.contains(&identifier) {
+                identifier = format!("{}{}", base, counter);
+                counter += 1;
+            }
+            used.insert(identifier.clone());
+            identifier
+        };
+        #[cfg(feature = "tracing")]
+        tracing::debug!(slug = element_id.as_str(), %identifier, "assigned identifier");
         identifier
     }
 
     /// Look up the hierarchical DSL path for an ElementId, falling back to the
     /// raw id string if the element was not registered (e.g. external refs).
-    fn resolve_path(&self, id: &ElementId) -> String {
-        self.id_to_path
+    fn resolve_path(paths: &HashMap<ElementId, String>, id: &ElementId) -> String {
+        paths
             .get(id)
             .cloned()
             .unwrap_or_else(|| id.as_str().to_string())
     }
 
-    fn write_model_section(&mut self) -> Result<(), DslError> {
+    /// Returns the element's own identifier, i.e. the last segment of its path.
+    fn local_identifier(&self, id: &ElementId) -> String {
+        let path = Self::resolve_path(&self.id_to_path, id);
+        match path.rsplit_once('.') {
+            Some((_, identifier)) => identifier.to_string(),
+            None => path,
+        }
+    }
+
+    fn resolve_endpoint(
+        &self,
+        paths: &HashMap<ElementId, String>,
+        endpoint: &RelationshipEndpoint,
+    ) -> Result<String, DslError> {
+        if let Some(code) = self
+            .endpoint_id(endpoint)
+            .and_then(|id| self.code_element(id))
+        {
+            return Err(DslError::CodeElementRelationship {
+                name: code.name().to_string(),
+            });
+        }
+        match endpoint {
+            RelationshipEndpoint::Id(id) => Ok(Self::resolve_path(paths, id)),
+            RelationshipEndpoint::Registered(id) => paths
+                .get(id)
+                .cloned()
+                .ok_or_else(|| DslError::ElementNotFound(id.to_string())),
+            RelationshipEndpoint::Identifier(identifier) => {
+                if self.identifier_mode == IdentifierMode::Flat && identifier.contains('.') {
+                    return Err(DslError::HierarchicalIdInFlatMode {
+                        id: identifier.clone(),
+                    });
+                }
+                Ok(identifier.clone())
+            }
+            RelationshipEndpoint::Name(name) => {
+                let matches = self.element_ids_named(name);
+                match matches.as_slice() {
+                    [id] => Ok(Self::resolve_path(paths, id)),
+                    [] => Err(DslError::ElementNotFound(name.clone())),
+                    _ => Err(DslError::AmbiguousRelationshipEndpoint { name: name.clone() }),
+                }
+            }
+        }
+    }
+
+    /// Picks a default description for a relationship from the types of its
+    /// endpoints. Endpoints given as literal identifiers have no known type.
+    fn infer_description(&self, rel: &StoredRelationship) -> &'static str {
+        let target_is_database = self.endpoint_id(&rel.target).is_some_and(|id| {
+            self.software_systems
+                .iter()
+                .flat_map(|s| s.containers())
+                .any(|c| c.id() == id && c.container_type() == ContainerType::Database)
+        });
+        let source_is_person = self
+            .endpoint_id(&rel.source)
+            .is_some_and(|id| self.persons.iter().any(|p| p.id() == id));
+        if target_is_database {
+            "Reads from and writes to"
+        } else if source_is_person {
+            "Uses"
+        } else {
+            "Depends on"
+        }
+    }
+
+    /// Returns the element id behind an endpoint, if it names exactly one
+    /// element of the model.
+    fn endpoint_id<'a>(&'a self, endpoint: &'a RelationshipEndpoint) -> Option<&'a ElementId> {
+        match endpoint {
+            RelationshipEndpoint::Id(id) | RelationshipEndpoint::Registered(id) => Some(id),
+            RelationshipEndpoint::Name(name) => match self.element_ids_named(name).as_slice() {
+                [id] => Some(id),
+                _ => None,
+            },
+            RelationshipEndpoint::Identifier(_) => None,
+        }
+    }
+
+    /// Returns the code element with the given id, if the model has one.
+    fn code_element(&self, id: &ElementId) -> Option<&CodeElement> {
+        self.software_systems
+            .iter()
+            .flat_map(|s| s.containers())
+            .flat_map(|c| c.components())
+            .flat_map(|c| c.code_elements())
+            .find(|code| code.id() == id)
+    }
+
+    /// Collects the ids of every element in the model with the given name.
+    fn element_ids_named(&self, name: &str) -> Vec<&ElementId> {
+        self.model_elements()
+            .into_iter()
+            .filter(|e| e.name() == name)
+            .map(|e| e.id())
+            .collect()
+    }
+
+    /// Returns every person, software system, container, component, and
+    /// code element in model order.
+    fn model_elements(&self) -> Vec<&dyn Element> {
+        let mut elements: Vec<&dyn Element> = Vec::new();
         for person in &self.persons {
-            let identifier = Self::resolve_identifier(person.id(), &mut self.used_identifiers);
-            self.id_to_path
-                .insert(person.id().clone(), identifier.clone());
+            elements.push(person);
+        }
+        for system in &self.software_systems {
+            elements.push(system);
+            for container in system.containers() {
+                elements.push(container);
+                for component in container.components() {
+                    elements.push(component);
+                    for code in component.code_elements() {
+                        elements.push(code);
+                    }
+                }
+            }
+        }
+        elements
+    }
+
+    /// Top-level location filters in output order. With an enterprise
+    /// boundary, internal elements are written first (inside the boundary)
+    /// followed by external ones.
+    fn location_passes(&self) -> Vec<Option<Location>> {
+        if self.enterprise_boundary.is_some() {
+            vec![Some(Location::Internal), Some(Location::External)]
+        } else {
+            vec![None]
+        }
+    }
+
+    fn persons_at(&self, location: Option<Location>) -> impl Iterator<Item = &Person> {
+        self.persons
+            .iter()
+            .filter(move |p| location.as_ref().is_none_or(|l| &p.location() == l))
+    }
+
+    fn software_systems_at(
+        &self,
+        location: Option<Location>,
+    ) -> impl Iterator<Item = &SoftwareSystem> {
+        self.software_systems
+            .iter()
+            .filter(move |s| location.as_ref().is_none_or(|l| &s.location() == l))
+    }
+
+    /// Assigns a unique identifier to every declared element in output order
+    /// and returns each element's DSL path. Code elements are only written as
+    /// component properties, so they get none. Explicitly pinned identifiers
+    /// are reserved first so auto-generated ones are suffixed around them; two
+    /// elements pinned to the same identifier are an error.
+    pub(crate) fn assign_paths(&self) -> Result<HashMap<ElementId, String>, DslError> {
+        let mut used = HashSet::new();
+        for element in self.model_elements() {
+            if element.element_type() == ElementType::Code {
+                continue;
+            }
+            let id = element.id();
+            if id.is_explicit() && !used.insert(id.as_str().to_string()) {
+                return Err(DslError::DuplicateExplicitIdentifier {
+                    id: id.as_str().to_string(),
+                });
+            }
+        }
+
+        let mut paths = HashMap::new();
+        for location in self.location_passes() {
+            for person in self.persons_at(location.clone()) {
+                let identifier = Self::resolve_identifier(person.id(), &mut used);
+                paths.insert(person.id().clone(), identifier);
+            }
+            for system in self.software_systems_at(location) {
+                let system_path = Self::resolve_identifier(system.id(), &mut used);
+                for container in system.containers() {
+                    let identifier = Self::resolve_identifier(container.id(), &mut used);
+                    let container_path = self.identifier_mode.child_path(&system_path, &identifier);
+                    for component in container.components() {
+                        let identifier = Self::resolve_identifier(component.id(), &mut used);
+                        let component_path = self
+                            .identifier_mode
+                            .child_path(&container_path, &identifier);
+                        paths.insert(component.id().clone(), component_path);
+                    }
+                    paths.insert(container.id().clone(), container_path);
+                }
+                paths.insert(system.id().clone(), system_path);
+            }
+        }
+        Ok(paths)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            fields(
+                persons = self.persons.len(),
+                software_systems = self.software_systems.len(),
+                relationships = self.relationships.len(),
+            )
+        )
+    )]
+    fn write_model_section(&mut self) -> Result<(), DslError> {
+        if let Some(boundary) = self.enterprise_boundary.clone() {
+            let has_internal = self
+                .persons
+                .iter()
+                .map(|p| p.location())
+                .chain(self.software_systems.iter().map(|s| s.location()))
+                .any(|l| l == Location::Internal);
+            if has_internal {
+                self.writer
+                    .add_line(&format!(r#"group "{}" {{"#, escape_dsl_string(&boundary)));
+                self.writer.indent();
+                self.write_persons(Some(Location::Internal))?;
+                self.write_software_systems(Some(Location::Internal))?;
+                self.writer.unindent();
+                self.writer.add_line("}");
+            }
+            self.write_persons(Some(Location::External))?;
+            self.write_software_systems(Some(Location::External))?;
+        } else {
+            self.write_persons(None)?;
+            self.write_software_systems(None)?;
+        }
+
+        self.write_relationships()
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(count = self.relationships.len()))
+    )]
+    fn write_relationships(&mut self) -> Result<(), DslError> {
+        for rel in &self.relationships {
+            if self.inline_source(rel).is_some() {
+                continue;
+            }
+            let source_path = self.resolve_endpoint(&self.id_to_path, &rel.source)?;
+            let dsl = self.format_stored_relationship(rel, &source_path)?;
+            Self::write_relationship(&mut self.writer, &dsl, rel);
+        }
+
+        Ok(())
+    }
+
+    /// Formats a relationship from `source`, which is either a resolved DSL
+    /// path or `this` for relationships written inside their source element.
+    fn format_stored_relationship(
+        &self,
+        rel: &StoredRelationship,
+        source: &str,
+    ) -> Result<String, DslError> {
+        let target_path = self.resolve_endpoint(&self.id_to_path, &rel.target)?;
+        let description = if self.inferred_descriptions && rel.description.is_empty() {
+            self.infer_description(rel)
+        } else {
+            &rel.description
+        };
+        Ok(writer::format_relationship(
+            source,
+            &target_path,
+            description,
+            rel.technology.as_deref(),
+        ))
+    }
+
+    fn write_relationship(writer: &mut DslWriter, dsl: &str, rel: &StoredRelationship) {
+        if rel.url.is_none() && rel.tags.is_empty() {
+            writer.add_line(dsl);
+            return;
+        }
+        // All optional attributes share one block; a second block after
+        // the relationship would be invalid DSL.
+        writer.write_block(dsl, |w| {
+            if let Some(url) = &rel.url {
+                w.add_line(&format!(r#"url "{}""#, escape_dsl_string(url)));
+            }
+            if !rel.tags.is_empty() {
+                w.add_line(&Self::tags_line(rel.tags.iter().map(String::as_str)));
+            }
+        });
+    }
+
+    /// Returns the element a relationship is written inside of when inline
+    /// relationships are enabled. Code elements have no block of their own.
+    fn inline_source<'a>(&'a self, rel: &'a StoredRelationship) -> Option<&'a ElementId> {
+        if !self.inline_relationships {
+            return None;
+        }
+        let id = self.endpoint_id(&rel.source)?;
+        self.model_elements()
+            .into_iter()
+            .any(|e| e.id() == id && e.element_type() != ElementType::Code)
+            .then_some(id)
+    }
+
+    /// Formats the relationships written inside the element with the given
+    /// id, each paired with its index in `relationships` for its url and
+    /// tags.
+    fn inline_relationships_of(&self, id: &ElementId) -> Result<Vec<(String, usize)>, DslError> {
+        self.relationships
+            .iter()
+            .enumerate()
+            .filter(|(_, rel)| self.inline_source(rel) == Some(id))
+            .map(|(index, rel)| Ok((self.format_stored_relationship(rel, "this")?, index)))
+            .collect()
+    }
+
+    /// Writes persons, optionally restricted to those with the given location.
+    fn write_persons(&mut self, location: Option<Location>) -> Result<(), DslError> {
+        for person in self
+            .persons
+            .iter()
+            .filter(|p| location.as_ref().is_none_or(|l| &p.location() == l))
+        {
+            let identifier = self.local_identifier(person.id());
             let dsl = Self::serialize_person(person, &identifier)?;
-            self.writer.add_line(&dsl);
+            if let Some(comment) = person.comment() {
+                self.writer.add_comment(comment);
+            }
+            let tags = Self::element_tags(person);
+            let inline = self.inline_relationships_of(person.id())?;
+            if tags.is_empty() && person.technology().is_none() && inline.is_empty() {
+                self.writer.add_line(&dsl);
+                continue;
+            }
+            self.writer.write_block(&dsl, |w| {
+                Self::write_tags(w, &tags);
+                if let Some(technology) = person.technology() {
+                    w.write_block("properties", |w| {
+                        w.add_line(&format!(
+                            r#""technology" "{}""#,
+                            escape_dsl_string(technology)
+                        ));
+                    });
+                }
+                for (dsl, index) in &inline {
+                    Self::write_relationship(w, dsl, &self.relationships[*index]);
+                }
+            });
         }
+        Ok(())
+    }
 
-        for system in &self.software_systems {
-            let system_identifier =
-                Self::resolve_identifier(system.id(), &mut self.used_identifiers);
-            self.id_to_path
-                .insert(system.id().clone(), system_identifier.clone());
+    /// Writes software systems and their nested containers and components,
+    /// optionally restricted to systems with the given location.
+    fn write_software_systems(&mut self, location: Option<Location>) -> Result<(), DslError> {
+        for system in self
+            .software_systems
+            .iter()
+            .filter(|s| location.as_ref().is_none_or(|l| &s.location() == l))
+        {
+            let system_identifier = self.local_identifier(system.id());
 
-            let has_containers = !system.containers().is_empty();
+            let inline = self.inline_relationships_of(system.id())?;
+            let tags = Self::element_tags(system);
+            let has_block =
+                !system.containers().is_empty() || !tags.is_empty() || !inline.is_empty();
 
-            let dsl = Self::serialize_software_system(system, &system_identifier, has_containers);
+            let dsl = Self::serialize_software_system(system, &system_identifier, has_block);
+            if let Some(comment) = system.comment() {
+                self.writer.add_comment(comment);
+            }
             self.writer.add_line(&dsl);
 
-            if has_containers {
+            if has_block {
                 self.writer.indent();
+                Self::write_tags(&mut self.writer, &tags);
                 for container in system.containers() {
-                    let container_identifier =
-                        Self::resolve_identifier(container.id(), &mut self.used_identifiers);
-                    let hierarchical_path =
-                        format!("{}.{}", system_identifier, container_identifier);
-                    self.id_to_path
-                        .insert(container.id().clone(), hierarchical_path);
-
-                    let has_components = !container.components().is_empty();
+                    let container_identifier = self.local_identifier(container.id());
+
+                    let container_inline = self.inline_relationships_of(container.id())?;
+                    let container_tags = Self::element_tags(container);
+                    let has_children = !container.components().is_empty()
+                        || !container_tags.is_empty()
+                        || container.instances().is_some()
+                        || !container_inline.is_empty();
                     let container_dsl =
-                        Self::serialize_container(container, &container_identifier, has_components);
+                        Self::serialize_container(container, &container_identifier, has_children);
+                    if let Some(comment) = container.comment() {
+                        self.writer.add_comment(comment);
+                    }
                     self.writer.add_line(&container_dsl);
 
-                    if has_components {
+                    if has_children {
                         self.writer.indent();
+                        Self::write_tags(&mut self.writer, &container_tags);
+                        if let Some(instances) = container.instances() {
+                            self.writer.write_block("properties", |w| {
+                                w.add_line(&format!(r#""instances" "{}""#, instances));
+                            });
+                        }
                         for component in container.components() {
-                            let component_identifier = Self::resolve_identifier(
-                                component.id(),
-                                &mut self.used_identifiers,
-                            );
-                            let hierarchical_path = format!(
-                                "{}.{}.{}",
-                                system_identifier, container_identifier, component_identifier
-                            );
-                            self.id_to_path
-                                .insert(component.id().clone(), hierarchical_path);
+                            let component_identifier = self.local_identifier(component.id());
                             let component_dsl =
                                 Self::serialize_component(component, &component_identifier)?;
-                            self.writer.add_line(&component_dsl);
+                            if let Some(comment) = component.comment() {
+                                self.writer.add_comment(comment);
+                            }
+                            let component_inline = self.inline_relationships_of(component.id())?;
+                            let component_tags = Self::element_tags(component);
+                            if component.code_elements().is_empty()
+                                && component_tags.is_empty()
+                                && component_inline.is_empty()
+                            {
+                                self.writer.add_line(&component_dsl);
+                                continue;
+                            }
+                            self.writer.write_block(&component_dsl, |w| {
+                                Self::write_tags(w, &component_tags);
+                                if !component.code_elements().is_empty() {
+                                    w.write_block("properties", |w| {
+                                        for code in component.code_elements() {
+                                            if let Some(comment) = code.comment() {
+                                                w.add_comment(comment);
+                                            }
+                                            w.add_line(&format!(
+                                                r#""{}" "{}""#,
+                                                escape_dsl_string(code.name()),
+                                                escape_dsl_string(&Self::code_summary(code))
+                                            ));
+                                        }
+                                    });
+                                }
+                                for (dsl, index) in &component_inline {
+                                    Self::write_relationship(w, dsl, &self.relationships[*index]);
+                                }
+                            });
+                        }
+                        for (dsl, index) in &container_inline {
+                            Self::write_relationship(
+                                &mut self.writer,
+                                dsl,
+                                &self.relationships[*index],
+                            );
                         }
                         self.writer.unindent();
                         self.writer.add_line("}");
                     }
                 }
+                for (dsl, index) in &inline {
+                    Self::write_relationship(&mut self.writer, dsl, &self.relationships[*index]);
+                }
                 self.writer.unindent();
                 self.writer.add_line("}");
             }
         }
+        Ok(())
+    }
 
-        for rel in &self.relationships {
-            let source_path = self.resolve_path(&rel.source_id);
-            let target_path = self.resolve_path(&rel.target_id);
-            let dsl = writer::format_relationship(
-                &source_path,
-                &target_path,
-                &rel.description,
-                rel.technology.as_deref(),
-            );
-            self.writer.add_line(&dsl);
+    /// Writes the `tags` line for an element, if it has any tags.
+    fn write_tags(writer: &mut DslWriter, tags: &[&str]) {
+        if !tags.is_empty() {
+            writer.add_line(&Self::tags_line(tags.iter().copied()));
         }
+    }
 
-        Ok(())
+    /// Formats a `tags` line with each tag quoted.
+    fn tags_line<'a>(tags: impl Iterator<Item = &'a str>) -> String {
+        let quoted: Vec<String> = tags
+            .map(|t| format!(r#""{}""#, escape_dsl_string(t)))
+            .collect();
+        format!("tags {}", quoted.join(" "))
+    }
+
+    /// Tags written for any model element: the implicit `External` tag for
+    /// external elements followed by the element's own tags, without
+    /// duplicates.
+    fn element_tags(element: &dyn Element) -> Vec<&str> {
+        let mut tags: Vec<&str> = Vec::new();
+        if element.location() == Location::External {
+            tags.push("External");
+        }
+        for tag in element.tags() {
+            if !tags.contains(&tag.as_str()) {
+                tags.push(tag);
+            }
+        }
+        tags
     }
 
     fn serialize_person(person: &Person, identifier: &str) -> Result<String, DslError> {
-        let base = writer::format_element_assignment(
+        Ok(writer::format_element_assignment(
             identifier,
             "person",
             person.name(),
             person.description(),
             None,
-        );
-        if person.location() == c4rs_core::c4::Location::External {
-            Ok(format!(
-                r#"{}" {{
-    tags "External"
-}}"#,
-                base
-            ))
-        } else {
-            Ok(base)
-        }
+        ))
     }
 
     fn serialize_software_system(
         system: &SoftwareSystem,
         identifier: &str,
-        has_containers: bool,
+        has_block: bool,
     ) -> String {
         let base = writer::format_element_assignment(
             identifier,
@@ -276,23 +1566,14 @@ impl WorkspaceSerializer {
             system.description(),
             None,
         );
-        if has_containers {
-            let external_tag = if system.location() == c4rs_core::c4::Location::External {
-                "\n    tags \"External\""
-            } else {
-                ""
-            };
-            format!("{} {{{}", base, external_tag)
+        if has_block {
+            format!("{} {{", base)
         } else {
             base
         }
     }
 
-    fn serialize_container(
-        container: &Container,
-        identifier: &str,
-        has_components: bool,
-    ) -> String {
+    fn serialize_container(container: &Container, identifier: &str, has_children: bool) -> String {
         let base = writer::format_element_assignment(
             identifier,
             "container",
@@ -300,23 +1581,47 @@ impl WorkspaceSerializer {
             container.description(),
             None,
         );
-        if has_components {
+        if has_children {
             format!("{} {{", base)
         } else {
             format!("{} {{}}", base)
         }
     }
 
+    /// Summarizes a code element as `language: file_path`, falling back to
+    /// whichever is set, or its code type.
+    fn code_summary(code: &CodeElement) -> String {
+        match (code.language(), code.file_path()) {
+            (Some(language), Some(path)) => format!("{}: {}", language, path),
+            (Some(part), None) | (None, Some(part)) => part.to_string(),
+            (None, None) => code.code_type().to_string(),
+        }
+    }
+
+    /// A component's visibility is written as its tags argument, which needs
+    /// an empty technology placeholder when there is no technology.
     fn serialize_component(component: &Component, identifier: &str) -> Result<String, DslError> {
-        Ok(writer::format_element_assignment(
+        let technology = component.technology();
+        let Some(visibility) = component.visibility() else {
+            return Ok(writer::format_element_assignment(
+                identifier,
+                "component",
+                component.name(),
+                component.description(),
+                technology,
+            ));
+        };
+        let base = writer::format_element_assignment(
             identifier,
             "component",
             component.name(),
             component.description(),
-            component.technology(),
-        ))
+            Some(technology.unwrap_or("")),
+        );
+        Ok(format!(r#"{} "{}""#, base, visibility))
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
     fn write_views_section(&mut self) -> Result<(), DslError> {
         let views_dsl = self.views_serializer.serialize()?;
         if !views_dsl.is_empty() {
@@ -1,5 +1,8 @@
 use super::*;
-use c4rs_core::c4::{Person, SoftwareSystem};
+use c4rs_core::c4::{
+    CodeElement, CodeType, Component, ComponentVisibility, Container, ContainerType,
+    InteractionStyle, Person, Relationship, SoftwareSystem,
+};
 
 #[test]
 fn test_workspace_serializer_empty() {
@@ -10,6 +13,22 @@ fn test_workspace_serializer_empty() {
     assert!(result.contains("model {"));
 }
 
+#[test]
+fn test_workspace_header_without_name_or_description() {
+    let bare = WorkspaceSerializer::new().serialize().unwrap();
+    assert!(bare.starts_with("workspace {\n"));
+    assert!(!bare.contains(r#""Name""#));
+
+    let named = WorkspaceSerializer::new().name("Shop").serialize().unwrap();
+    assert!(named.starts_with("workspace \"Shop\" {\n"));
+
+    let described = WorkspaceSerializer::new()
+        .description("Online shop")
+        .serialize()
+        .unwrap();
+    assert!(described.starts_with(r#"workspace "Workspace" "Online shop" {"#));
+}
+
 #[test]
 fn test_workspace_serializer_with_person() {
     let person = Person::builder()
@@ -380,6 +399,101 @@ fn test_us3_relationship_order() {
     assert!(pos1 < pos2, "Relationships should appear in order added");
 }
 
+#[test]
+fn test_person_to_person_relationship() {
+    let user = Person::builder()
+        .name("User".into())
+        .description("A system user".into())
+        .build()
+        .unwrap();
+    let underwriter = Person::builder()
+        .name("Underwriter".into())
+        .description("Approves policies".into())
+        .build()
+        .unwrap();
+    let serializer = WorkspaceSerializer::new()
+        .add_person(&user)
+        .add_person(&underwriter)
+        .relate(&user, &underwriter, "Collaborates with")
+        .add_relationship(&underwriter, &user, "Reports to", None);
+    serializer.validate().unwrap();
+
+    let result = serializer.serialize().unwrap();
+    assert!(result.contains(r#"u = person "User" "A system user""#));
+    assert!(result.contains(r#"u1 = person "Underwriter" "Approves policies""#));
+    assert!(result.contains(r#"u -> u1 "Collaborates with""#));
+    assert!(result.contains(r#"u1 -> u "Reports to""#));
+}
+
+#[test]
+fn test_rendering_error_reports_section_and_line() {
+    let user = Person::builder()
+        .name("User".into())
+        .description("A system user".into())
+        .build()
+        .unwrap();
+    let mut serializer = WorkspaceSerializer::new().add_person(&user);
+    serializer.id_to_path = serializer.assign_paths().unwrap();
+    serializer.write_workspace_header().unwrap();
+    serializer.write_model_section().unwrap();
+    let line = serializer.writer.line_count();
+    assert!(line > 0);
+
+    let err = serializer.section_error("views", DslError::TemplateError("boom".to_string()));
+    assert!(matches!(
+        &err,
+        DslError::InSection { section, line: at, .. } if section == "views" && *at == line
+    ));
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "error in views section after line {}: template error: boom",
+            line
+        )
+    );
+
+    let not_found = serializer.section_error("model", DslError::ElementNotFound("o".to_string()));
+    assert!(matches!(not_found, DslError::ElementNotFound(id) if id == "o"));
+}
+
+#[test]
+fn test_relationship_url_and_tags_share_one_block() {
+    let user = Person::builder()
+        .name("User".into())
+        .description("A system user".into())
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("System".into())
+        .description("A system".into())
+        .build()
+        .unwrap();
+    let relationship = Relationship::builder()
+        .source(user.clone())
+        .target(system.clone())
+        .description("Sends events to".into())
+        .technology("Kafka".into())
+        .interaction_style(InteractionStyle::Asynchronous)
+        .url("https://example.com/events".into())
+        .tags(vec!["Events".into()])
+        .build()
+        .unwrap();
+
+    let result = WorkspaceSerializer::new()
+        .add_person(&user)
+        .add_software_system(&system)
+        .add_typed_relationship(&relationship)
+        .add_relationship(&system, &user, "Notifies", None)
+        .serialize()
+        .unwrap();
+
+    assert!(result.contains(
+        "        u -> s \"Sends events to\" \"Kafka\" {\n            url \"https://example.com/events\"\n            tags \"Asynchronous\" \"Events\"\n        }\n"
+    ));
+    assert_eq!(result.matches("u -> s").count(), 1);
+    assert!(result.contains("        s -> u \"Notifies\"\n"));
+}
+
 #[test]
 fn test_us7_brace_balance() {
     let person = Person::builder()
@@ -473,3 +587,967 @@ fn test_relationship_with_special_chars() {
     assert!(result.contains(r#""Uses \"HTTPS\""#));
     assert!(result.contains(r#""JSON\\API""#));
 }
+
+#[test]
+fn test_flat_mode_rejects_dotted_identifier() {
+    let result = WorkspaceSerializer::new()
+        .identifier_mode(IdentifierMode::Flat)
+        .add_relationship_by_identifier("s.a", "s.d", "Reads from", None)
+        .serialize();
+
+    assert!(matches!(
+        result,
+        Err(DslError::HierarchicalIdInFlatMode { id }) if id == "s.a"
+    ));
+}
+
+#[test]
+fn test_hierarchical_mode_accepts_dotted_identifier() {
+    let result = WorkspaceSerializer::new()
+        .identifier_mode(IdentifierMode::Hierarchical)
+        .add_relationship_by_identifier("s.a", "s.d", "Reads from", None)
+        .serialize()
+        .unwrap();
+
+    assert!(result.contains("!identifiers hierarchical"));
+    assert!(result.contains(r#"s.a -> s.d "Reads from""#));
+}
+
+#[test]
+fn test_component_visibility_tag() {
+    let api = Component::builder()
+        .name("Facade".into())
+        .description("Entry point".into())
+        .technology("Rust".into())
+        .visibility(ComponentVisibility::Public)
+        .build()
+        .unwrap();
+    let helper = Component::builder()
+        .name("Parser".into())
+        .description("Parses input".into())
+        .visibility(ComponentVisibility::Internal)
+        .build()
+        .unwrap();
+    let container = Container::builder()
+        .name("Library".into())
+        .description("A library".into())
+        .container_type(ContainerType::Other("Library".into()))
+        .add_component(&api)
+        .add_component(&helper)
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("System".into())
+        .description("A system".into())
+        .add_container(&container)
+        .build()
+        .unwrap();
+
+    let result = WorkspaceSerializer::new()
+        .add_software_system(&system)
+        .serialize()
+        .unwrap();
+
+    assert!(result.contains(r#"f = component "Facade" "Entry point" "Rust" "Public API""#));
+    assert!(result.contains(r#"p = component "Parser" "Parses input" "" "Internal""#));
+}
+
+#[test]
+fn test_code_elements_as_component_properties() {
+    let handler = CodeElement::builder()
+        .name("handle_request".into())
+        .description("Entry point".into())
+        .code_type(CodeType::Function)
+        .language("Rust".into())
+        .file_path("src/handler.rs".into())
+        .build()
+        .unwrap();
+    let router = CodeElement::builder()
+        .name("Router".into())
+        .description("Routes requests".into())
+        .code_type(CodeType::Struct)
+        .language("Rust".into())
+        .file_path("src/router.rs".into())
+        .build()
+        .unwrap();
+    let component = Component::builder()
+        .name("Handler".into())
+        .description("Handles requests".into())
+        .technology("Rust".into())
+        .add_code_element(&handler)
+        .add_code_element(&router)
+        .build()
+        .unwrap();
+    let container = Container::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .container_type(ContainerType::Api)
+        .add_component(&component)
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("System".into())
+        .description("A system".into())
+        .add_container(&container)
+        .build()
+        .unwrap();
+
+    let result = WorkspaceSerializer::new()
+        .add_software_system(&system)
+        .serialize()
+        .unwrap();
+
+    assert!(result.contains(
+        r#"                h = component "Handler" "Handles requests" "Rust" {
+                    properties {
+                        "handle_request" "Rust: src/handler.rs"
+                        "Router" "Rust: src/router.rs"
+                    }
+                }"#
+    ));
+}
+
+#[test]
+fn test_other_code_type_summary() {
+    let log = CodeElement::builder()
+        .name("log".into())
+        .description("Logging macro".into())
+        .code_type(CodeType::Other("macro".into()))
+        .build()
+        .unwrap();
+    let component = Component::builder()
+        .name("Handler".into())
+        .description("Handles requests".into())
+        .add_code_element(&log)
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("System".into())
+        .description("A system".into())
+        .add_container(
+            &Container::builder()
+                .name("API".into())
+                .description("Backend".into())
+                .container_type(ContainerType::Api)
+                .add_component(&component)
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    let result = WorkspaceSerializer::new()
+        .add_software_system(&system)
+        .serialize()
+        .unwrap();
+    assert!(result.contains(r#""log" "macro""#));
+}
+
+#[test]
+fn test_component_multiple_technologies() {
+    let component = Component::builder()
+        .name("Handler".into())
+        .description("Handles requests".into())
+        .add_technology("Rust".into())
+        .add_technology("Axum".into())
+        .add_technology("Tokio".into())
+        .build()
+        .unwrap();
+    let container = Container::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .container_type(ContainerType::Api)
+        .add_component(&component)
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("System".into())
+        .description("A system".into())
+        .add_container(&container)
+        .build()
+        .unwrap();
+
+    let result = WorkspaceSerializer::new()
+        .add_software_system(&system)
+        .serialize()
+        .unwrap();
+
+    assert!(result.contains(r#"h = component "Handler" "Handles requests" "Rust, Axum, Tokio""#));
+}
+
+#[test]
+fn test_explicit_identifier_reserved_before_generated() {
+    let generated = SoftwareSystem::builder()
+        .name("Alpha".into())
+        .description("First".into())
+        .build()
+        .unwrap();
+    let pinned = SoftwareSystem::builder()
+        .name("Bravo".into())
+        .description("Second".into())
+        .build()
+        .unwrap()
+        .with_identifier("a");
+
+    let result = WorkspaceSerializer::new()
+        .add_software_system(&generated)
+        .add_software_system(&pinned)
+        .add_relationship(&generated, &pinned, "Calls", None)
+        .serialize()
+        .unwrap();
+
+    assert!(result.contains(r#"a1 = softwareSystem "Alpha""#));
+    assert!(result.contains(r#"a = softwareSystem "Bravo""#));
+    assert!(result.contains(r#"a1 -> a "Calls""#));
+}
+
+#[test]
+fn test_duplicate_explicit_identifier() {
+    let first = SoftwareSystem::builder()
+        .name("Alpha".into())
+        .description("First".into())
+        .build()
+        .unwrap()
+        .with_identifier("core");
+    let second = SoftwareSystem::builder()
+        .name("Bravo".into())
+        .description("Second".into())
+        .build()
+        .unwrap()
+        .with_identifier("core");
+
+    let serializer = WorkspaceSerializer::new()
+        .add_software_system(&first)
+        .add_software_system(&second);
+
+    assert!(matches!(
+        serializer.warnings(),
+        Err(DslError::DuplicateExplicitIdentifier { id }) if id == "core"
+    ));
+    assert!(matches!(
+        serializer.serialize(),
+        Err(DslError::DuplicateExplicitIdentifier { id }) if id == "core"
+    ));
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn test_tracing_model_section_span() {
+    use std::sync::{Arc, Mutex};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, subscriber::Subscriber};
+
+    struct SpanRecorder(Arc<Mutex<Vec<String>>>);
+
+    impl Subscriber for SpanRecorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut names = self.0.lock().unwrap();
+            names.push(span.metadata().name().to_string());
+            Id::from_u64(names.len() as u64)
+        }
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, _: &Event<'_>) {}
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    let person = Person::builder()
+        .name("User".into())
+        .description("A user".into())
+        .build()
+        .unwrap();
+    let spans = Arc::new(Mutex::new(Vec::new()));
+    let result = tracing::subscriber::with_default(SpanRecorder(spans.clone()), || {
+        WorkspaceSerializer::new().add_person(&person).serialize()
+    })
+    .unwrap();
+
+    assert!(result.contains("u = person"));
+    let spans = spans.lock().unwrap();
+    assert!(spans.iter().any(|name| name == "write_model_section"));
+    assert!(spans.iter().any(|name| name == "write_relationships"));
+}
+
+#[test]
+fn test_person_technology_property() {
+    let person = Person::builder()
+        .name("API Consumer".into())
+        .description("External integrator".into())
+        .location(c4rs_core::c4::Location::External)
+        .technology("Python 3.11".into())
+        .build()
+        .unwrap();
+    let result = WorkspaceSerializer::new()
+        .add_person(&person)
+        .serialize()
+        .unwrap();
+
+    assert!(result.contains(
+        r#"        ac = person "API Consumer" "External integrator" {
+            tags "External"
+            properties {
+                "technology" "Python 3.11"
+            }
+        }"#
+    ));
+}
+
+#[test]
+fn test_detect_relationship_cycle() {
+    let system = |name: &str| {
+        SoftwareSystem::builder()
+            .name(name.into())
+            .description("A system".into())
+            .build()
+            .unwrap()
+    };
+    let (alpha, bravo, charlie, delta) = (
+        system("Alpha"),
+        system("Bravo"),
+        system("Charlie"),
+        system("Delta"),
+    );
+    let serializer = WorkspaceSerializer::new()
+        .add_software_system(&alpha)
+        .add_software_system(&bravo)
+        .add_software_system(&charlie)
+        .add_software_system(&delta)
+        .add_relationship(&alpha, &bravo, "Calls", None)
+        .add_relationship(&bravo, &charlie, "Calls", None)
+        .add_relationship(&charlie, &alpha, "Calls", None)
+        .add_relationship(&charlie, &delta, "Calls", None);
+
+    let cycles = serializer.detect_cycles();
+
+    assert_eq!(cycles, vec![vec!["a", "b", "c"]]);
+    assert!(
+        serializer
+            .warnings()
+            .unwrap()
+            .contains(&DslWarning::RelationshipCycle {
+                cycle: vec!["a".into(), "b".into(), "c".into()],
+            })
+    );
+}
+
+#[test]
+fn test_detect_overlapping_relationship_cycles() {
+    let system = |name: &str| {
+        SoftwareSystem::builder()
+            .name(name.into())
+            .description("A system".into())
+            .build()
+            .unwrap()
+    };
+    let (alpha, bravo, charlie) = (system("Alpha"), system("Bravo"), system("Charlie"));
+    let serializer = WorkspaceSerializer::new()
+        .add_software_system(&alpha)
+        .add_software_system(&bravo)
+        .add_software_system(&charlie)
+        .add_relationship(&alpha, &bravo, "Calls", None)
+        .add_relationship(&bravo, &charlie, "Calls", None)
+        .add_relationship(&charlie, &alpha, "Calls", None)
+        .add_relationship(&alpha, &charlie, "Calls", None)
+        .add_relationship_by_name("Alpha", "Missing", "Calls", None);
+
+    let cycles = serializer.detect_cycles();
+
+    assert_eq!(cycles, vec![vec!["a", "b", "c"], vec!["a", "c"]]);
+}
+
+#[test]
+fn test_relate_nested_components() {
+    let handler = Component::builder()
+        .name("Handler".into())
+        .description("Handles requests".into())
+        .build()
+        .unwrap();
+    let repository = Component::builder()
+        .name("Repository".into())
+        .description("Stores data".into())
+        .build()
+        .unwrap();
+    let api = Container::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .container_type(ContainerType::Api)
+        .add_component(&handler)
+        .build()
+        .unwrap();
+    let worker = Container::builder()
+        .name("Worker".into())
+        .description("Background jobs".into())
+        .container_type(ContainerType::Other("Worker".into()))
+        .add_component(&repository)
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("Shop".into())
+        .description("Online shop".into())
+        .add_container(&api)
+        .add_container(&worker)
+        .build()
+        .unwrap();
+
+    let result = WorkspaceSerializer::new()
+        .add_software_system(&system)
+        .relate(&handler, &repository, "Reads from")
+        .serialize()
+        .unwrap();
+
+    assert!(result.contains(r#"s.a.h -> s.w.r "Reads from""#));
+}
+
+#[test]
+fn test_relate_unregistered_element() {
+    let user = Person::builder()
+        .name("User".into())
+        .description("A user".into())
+        .build()
+        .unwrap();
+    let outsider = SoftwareSystem::builder()
+        .name("Outsider".into())
+        .description("Never added".into())
+        .build()
+        .unwrap();
+
+    let result = WorkspaceSerializer::new()
+        .add_person(&user)
+        .relate(&user, &outsider, "Uses")
+        .serialize();
+
+    assert!(matches!(result, Err(DslError::ElementNotFound(id)) if id == "o"));
+}
+
+#[test]
+fn test_relate_code_elements_is_rejected() {
+    let parse = CodeElement::builder()
+        .name("parse".into())
+        .description("Parses the request".into())
+        .code_type(CodeType::Function)
+        .build()
+        .unwrap();
+    let validate = CodeElement::builder()
+        .name("validate".into())
+        .description("Validates the request".into())
+        .code_type(CodeType::Function)
+        .build()
+        .unwrap();
+    let handler = Component::builder()
+        .name("Handler".into())
+        .description("Handles requests".into())
+        .add_code_element(&parse)
+        .add_code_element(&validate)
+        .build()
+        .unwrap();
+    let api = Container::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .container_type(ContainerType::Api)
+        .add_component(&handler)
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("Shop".into())
+        .description("Online shop".into())
+        .add_container(&api)
+        .build()
+        .unwrap();
+
+    let result = WorkspaceSerializer::new()
+        .add_software_system(&system)
+        .relate(&parse, &validate, "Calls")
+        .serialize();
+
+    assert!(matches!(
+        result,
+        Err(DslError::CodeElementRelationship { name }) if name == "parse"
+    ));
+}
+
+#[test]
+fn test_code_elements_do_not_shift_identifiers() {
+    let code = |name: &str| {
+        CodeElement::builder()
+            .name(name.into())
+            .description("Module".into())
+            .code_type(CodeType::Module)
+            .build()
+            .unwrap()
+    };
+    let handler = Component::builder()
+        .name("Handler".into())
+        .description("Handles requests".into())
+        .add_code_element(&code("Cache"))
+        .add_code_element(&code("Store"))
+        .build()
+        .unwrap();
+    let api = Container::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .container_type(ContainerType::Api)
+        .add_component(&handler)
+        .build()
+        .unwrap();
+    let cli = Container::builder()
+        .name("Cli".into())
+        .description("Command line".into())
+        .container_type(ContainerType::Other("CLI".into()))
+        .build()
+        .unwrap();
+    let shop = SoftwareSystem::builder()
+        .name("Shop".into())
+        .description("Online shop".into())
+        .add_container(&api)
+        .add_container(&cli)
+        .build()
+        .unwrap();
+    let search = SoftwareSystem::builder()
+        .name("Search".into())
+        .description("Search engine".into())
+        .build()
+        .unwrap();
+
+    let result = WorkspaceSerializer::new()
+        .add_software_system(&shop)
+        .add_software_system(&search)
+        .relate(&cli, &search, "Queries")
+        .serialize()
+        .unwrap();
+
+    assert!(result.contains(r#"c = container "Cli""#));
+    assert!(result.contains(r#"s1 = softwareSystem "Search""#));
+    assert!(result.contains(r#"s.c -> s1 "Queries""#));
+}
+
+#[test]
+fn test_technology_mismatch_warning() {
+    let build = |component_technology: &str| {
+        let component = Component::builder()
+            .name("Handler".into())
+            .description("Handles requests".into())
+            .technology(component_technology.into())
+            .build()
+            .unwrap();
+        let container = Container::builder()
+            .name("API".into())
+            .description("Backend".into())
+            .container_type(ContainerType::Api)
+            .technology("Go".into())
+            .add_technology("gRPC".into())
+            .add_component(&component)
+            .build()
+            .unwrap();
+        SoftwareSystem::builder()
+            .name("Shop".into())
+            .description("Online shop".into())
+            .add_container(&container)
+            .build()
+            .unwrap()
+    };
+
+    let (_, warnings) = WorkspaceSerializer::new()
+        .add_software_system(&build("Rust"))
+        .serialize_with_warnings()
+        .unwrap();
+    assert_eq!(
+        warnings,
+        vec![DslWarning::TechnologyMismatch {
+            container: "API".into(),
+            component: "Handler".into(),
+        }]
+    );
+
+    let (_, warnings) = WorkspaceSerializer::new()
+        .add_software_system(&build("Go"))
+        .serialize_with_warnings()
+        .unwrap();
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn test_hyphenated_pinned_identifier_is_referenced_consistently() {
+    let api = Container::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .container_type(ContainerType::Api)
+        .build()
+        .unwrap()
+        .with_identifier("api-gateway");
+    let system = SoftwareSystem::builder()
+        .name("Shop".into())
+        .description("Online shop".into())
+        .add_container(&api)
+        .build()
+        .unwrap();
+    let user = Person::builder()
+        .name("User".into())
+        .description("A user".into())
+        .build()
+        .unwrap();
+
+    let result = WorkspaceSerializer::new()
+        .add_person(&user)
+        .add_software_system(&system)
+        .add_relationship(&user, &api, "Calls", None)
+        .serialize()
+        .unwrap();
+    assert!(
+        result.contains("api_gateway = container \"API\""),
+        "{result}"
+    );
+    assert!(result.contains("u -> s.api_gateway \"Calls\""), "{result}");
+    assert!(!result.contains("api-gateway"));
+
+    let other = Container::builder()
+        .name("Worker".into())
+        .description("Jobs".into())
+        .container_type(ContainerType::Api)
+        .build()
+        .unwrap()
+        .with_identifier("api_gateway");
+    let clash = SoftwareSystem::builder()
+        .name("Shop".into())
+        .description("Online shop".into())
+        .add_container(&api)
+        .add_container(&other)
+        .build()
+        .unwrap();
+    assert!(matches!(
+        WorkspaceSerializer::new().add_software_system(&clash).serialize(),
+        Err(DslError::DuplicateExplicitIdentifier { id }) if id == "api_gateway"
+    ));
+}
+
+#[test]
+fn test_long_identifier_warning() {
+    let segment = "segment".repeat(20);
+    let container = Container::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .container_type(ContainerType::Api)
+        .build()
+        .unwrap()
+        .with_identifier(&format!("api{segment}"));
+    let system = SoftwareSystem::builder()
+        .name("Platform".into())
+        .description("Core".into())
+        .add_container(&container)
+        .build()
+        .unwrap()
+        .with_identifier(&segment);
+    let path = format!("{segment}.api{segment}");
+
+    let (_, warnings) = WorkspaceSerializer::new()
+        .add_software_system(&system)
+        .serialize_with_warnings()
+        .unwrap();
+    assert_eq!(
+        warnings,
+        vec![DslWarning::LongIdentifier {
+            id: path.clone(),
+            length: 284,
+        }]
+    );
+
+    let (_, warnings) = WorkspaceSerializer::new()
+        .add_software_system(&system)
+        .max_identifier_length(300)
+        .serialize_with_warnings()
+        .unwrap();
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn test_component_view_scoped_to_system() {
+    let api = Container::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .container_type(ContainerType::Api)
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("Shop".into())
+        .description("Online shop".into())
+        .add_container(&api)
+        .build()
+        .unwrap();
+    let view = |identifier: &str| {
+        ViewConfiguration::builder()
+            .view_type(ViewType::Component)
+            .element_identifier(identifier.into())
+            .title("Components".into())
+            .build()
+    };
+
+    let serializer = WorkspaceSerializer::new()
+        .add_software_system(&system)
+        .add_view(view("s"));
+    let error = serializer.validate().unwrap_err();
+    assert!(matches!(
+        &error,
+        DslError::InvalidViewScope { view, expected, actual }
+            if view == "Components" && expected == "Container" && actual == "SoftwareSystem"
+    ));
+    assert!(matches!(
+        serializer.serialize(),
+        Err(DslError::InvalidViewScope { .. })
+    ));
+
+    let serializer = WorkspaceSerializer::new()
+        .add_software_system(&system)
+        .add_view(view("s.a"));
+    assert!(serializer.validate().is_ok());
+    assert!(serializer.serialize().is_ok());
+}
+
+#[test]
+fn test_default_view_for_container() {
+    let api = Container::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .container_type(ContainerType::Api)
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("Shop".into())
+        .description("Online shop".into())
+        .add_container(&api)
+        .build()
+        .unwrap();
+    let view = ViewConfiguration::default_for(&api, ViewType::Component);
+    assert_eq!(view.title, "API Components");
+    assert_eq!(view.scope(), Some(api.id()));
+
+    let (result, warnings) = WorkspaceSerializer::new()
+        .add_software_system(&system)
+        .add_view(view)
+        .serialize_with_warnings()
+        .unwrap();
+    assert!(warnings.is_empty());
+    assert!(
+        result.contains("component s.a \"API_Components\" {"),
+        "{result}"
+    );
+}
+
+#[test]
+fn test_external_person_with_user_tags() {
+    let person = Person::builder()
+        .name("Customer".into())
+        .description("A customer".into())
+        .location(Location::External)
+        .tags(vec!["VIP".into(), "External".into()])
+        .build()
+        .unwrap();
+
+    let result = WorkspaceSerializer::new()
+        .add_person(&person)
+        .serialize()
+        .unwrap();
+
+    assert!(result.contains(
+        "c = person \"Customer\" \"A customer\" {\n            tags \"External\" \"VIP\"\n        }"
+    ));
+    assert_eq!(result.matches("tags").count(), 1);
+}
+
+#[test]
+fn test_tag_with_comma_rejected() {
+    let person = Person::builder()
+        .name("Customer".into())
+        .description("A customer".into())
+        .tags(vec!["Team A, B".into()])
+        .build()
+        .unwrap();
+
+    let serializer = WorkspaceSerializer::new().add_person(&person);
+    assert!(matches!(
+        serializer.validate(),
+        Err(DslError::InvalidTag { tag }) if tag == "Team A, B"
+    ));
+    assert!(matches!(
+        serializer.serialize(),
+        Err(DslError::InvalidTag { .. })
+    ));
+}
+
+#[test]
+fn test_container_to_system_relationship() {
+    let web = Container::builder()
+        .name("Web App".into())
+        .description("Frontend".into())
+        .container_type(ContainerType::WebApplication)
+        .build()
+        .unwrap();
+    let shop = SoftwareSystem::builder()
+        .name("Shop".into())
+        .description("Online shop".into())
+        .add_container(&web)
+        .build()
+        .unwrap();
+    let payments = SoftwareSystem::builder()
+        .name("Payments".into())
+        .description("Card payments".into())
+        .build()
+        .unwrap();
+
+    let result = WorkspaceSerializer::new()
+        .add_software_system(&shop)
+        .add_software_system(&payments)
+        .add_relationship(&web, &payments, "Charges cards", None)
+        .add_relationship(&payments, &web, "Confirms", None)
+        .relate(&web, &payments, "Refunds")
+        .serialize()
+        .unwrap();
+
+    assert!(result.contains(r#"s.wa -> p "Charges cards""#));
+    assert!(result.contains(r#"p -> s.wa "Confirms""#));
+    assert!(result.contains(r#"s.wa -> p "Refunds""#));
+}
+
+#[test]
+fn test_container_tags() {
+    let queue = Container::builder()
+        .name("Queue".into())
+        .description("Jobs".into())
+        .container_type(ContainerType::MessageBus)
+        .tags(vec!["Async".into()])
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("Worker".into())
+        .description("Background jobs".into())
+        .add_container(&queue)
+        .build()
+        .unwrap();
+
+    let result = WorkspaceSerializer::new()
+        .add_software_system(&system)
+        .serialize()
+        .unwrap();
+    assert!(result.contains(
+        "q = container \"Queue\" \"Jobs\" {\n                tags \"Async\"\n            }"
+    ));
+}
+
+#[test]
+fn test_code_element_comment() {
+    let code = CodeElement::builder()
+        .name("handle".into())
+        .description("Entry point".into())
+        .code_type(CodeType::Function)
+        .language("Rust".into())
+        .build()
+        .unwrap()
+        .with_comment("Hot path");
+    let handler = Component::builder()
+        .name("Handler".into())
+        .description("Handles jobs".into())
+        .add_code_element(&code)
+        .build()
+        .unwrap();
+    let api = Container::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .container_type(ContainerType::Api)
+        .add_component(&handler)
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("Shop".into())
+        .description("Online shop".into())
+        .add_container(&api)
+        .build()
+        .unwrap();
+
+    let result = WorkspaceSerializer::new()
+        .add_software_system(&system)
+        .serialize()
+        .unwrap();
+    assert!(
+        result.contains("properties {\n                        # Hot path\n                        \"handle\" \"Rust\"\n"),
+        "{result}"
+    );
+}
+
+#[test]
+fn test_tags_on_every_element_type() {
+    let handler = Component::builder()
+        .name("Handler".into())
+        .description("Handles jobs".into())
+        .tags(vec!["Hot Path".into()])
+        .build()
+        .unwrap();
+    let queue = Container::builder()
+        .name("Queue".into())
+        .description("Jobs".into())
+        .container_type(ContainerType::MessageBus)
+        .add_component(&handler)
+        .build()
+        .unwrap();
+    let system = SoftwareSystem::builder()
+        .name("Worker".into())
+        .description("Background jobs".into())
+        .location(Location::External)
+        .tags(vec!["Legacy".into(), "External".into()])
+        .add_container(&queue)
+        .build()
+        .unwrap();
+    let elements: [&dyn Element; 3] = [&system, &queue, &handler];
+    assert_eq!(elements.map(|e| e.tags().len()), [2, 0, 1]);
+
+    let result = WorkspaceSerializer::new()
+        .add_software_system(&system)
+        .serialize()
+        .unwrap();
+    assert!(result.contains(
+        "w = softwareSystem \"Worker\" \"Background jobs\" {\n            tags \"External\" \"Legacy\"\n"
+    ), "{result}");
+    assert!(result.contains(
+        "h = component \"Handler\" \"Handles jobs\" {\n                    tags \"Hot Path\"\n                }"
+    ), "{result}");
+}
+
+#[test]
+fn test_container_instances_property() {
+    let api = Container::builder()
+        .name("API".into())
+        .description("Backend".into())
+        .container_type(ContainerType::Api)
+        .instances(3)
+        .build()
+        .unwrap();
+    assert_eq!(api.instances(), Some(3));
+    let system = SoftwareSystem::builder()
+        .name("Shop".into())
+        .description("Online shop".into())
+        .add_container(&api)
+        .build()
+        .unwrap();
+
+    let result = WorkspaceSerializer::new()
+        .add_software_system(&system)
+        .serialize()
+        .unwrap();
+    assert!(result.contains(
+        "a = container \"API\" \"Backend\" {\n                properties {\n                    \"instances\" \"3\"\n                }\n            }"
+    ), "{result}");
+}
+
+#[test]
+fn test_typed_properties() {
+    let result = WorkspaceSerializer::new()
+        .add_property_number("replicas", 3.0)
+        .add_property_number("ratio", 0.5)
+        .add_property_bool("public", false)
+        .serialize()
+        .unwrap();
+    assert!(result.contains(
+        "    properties {\n        \"public\" \"false\"\n        \"ratio\" \"0.5\"\n        \"replicas\" \"3\"\n    }\n"
+    ), "{result}");
+}
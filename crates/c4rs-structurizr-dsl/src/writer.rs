@@ -1,27 +1,92 @@
+use crate::error::DslError;
 use crate::templates::helpers::{escape_dsl_string, format_identifier};
 use std::fmt::{Display, Formatter};
 
+const INDENT: &str = "    ";
+
+/// Rough average length of an output line, used to size the buffer.
+const BYTES_PER_LINE: usize = 48;
+
+/// Line-oriented builder for DSL output. Lines are appended to a single
+/// buffer, separated by `\n` with no trailing newline.
 #[derive(Debug, Default)]
 pub struct DslWriter {
-    lines: Vec<String>,
+    output: String,
+    line_count: usize,
     indent_level: usize,
+    compact: bool,
+    omit_blank_lines: bool,
 }
 
 impl DslWriter {
     pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a writer with room for roughly `lines` output lines.
+    pub fn with_capacity(lines: usize) -> Self {
         Self {
-            lines: Vec::new(),
-            indent_level: 0,
+            output: String::with_capacity(lines * BYTES_PER_LINE),
+            ..Self::default()
         }
     }
 
+    fn push_line(&mut self, indent_level: usize, line: &str) {
+        if self.line_count > 0 {
+            self.output.push('\n');
+        }
+        self.line_count += 1 + line.matches('\n').count();
+        for _ in 0..indent_level {
+            self.output.push_str(INDENT);
+        }
+        self.output.push_str(line);
+    }
+
+    /// Switches between pretty output and compact output. Compact output
+    /// drops indentation and blank separator lines but keeps one statement
+    /// per line, since Structurizr DSL is line-oriented.
+    pub fn set_compact(&mut self, compact: bool) {
+        self.compact = compact;
+    }
+
+    pub fn is_compact(&self) -> bool {
+        self.compact
+    }
+
+    /// Keeps or drops blank separator lines while leaving indentation intact.
+    pub fn set_blank_lines(&mut self, blank_lines: bool) {
+        self.omit_blank_lines = !blank_lines;
+    }
+
+    pub fn has_blank_lines(&self) -> bool {
+        !self.omit_blank_lines
+    }
+
     pub fn add_line(&mut self, line: &str) {
-        let indent = "    ".repeat(self.indent_level);
-        self.lines.push(format!("{}{}", indent, line));
+        if self.compact {
+            for part in line.lines() {
+                let trimmed = part.trim_start();
+                if !trimmed.is_empty() {
+                    self.push_line(0, trimmed);
+                }
+            }
+            return;
+        }
+        self.push_line(self.indent_level, line);
+    }
+
+    /// Writes a `#` comment line for every line of `comment`.
+    pub fn add_comment(&mut self, comment: &str) {
+        for line in comment.lines() {
+            self.add_line(&format!("# {}", line));
+        }
     }
 
     pub fn add_empty_line(&mut self) {
-        self.lines.push(String::new());
+        if self.compact || self.omit_blank_lines {
+            return;
+        }
+        self.push_line(0, "");
     }
 
     pub fn indent(&mut self) {
@@ -45,12 +110,23 @@ impl DslWriter {
         self.add_line("}");
     }
 
+    /// Number of lines written so far, including blank lines.
+    pub fn line_count(&self) -> usize {
+        self.line_count
+    }
+
     pub fn as_output(&self) -> String {
-        self.lines.join("\n")
+        self.output.clone()
+    }
+
+    /// Returns the output without copying the buffer.
+    pub fn into_output(self) -> String {
+        self.output
     }
 
     pub fn clear(&mut self) {
-        self.lines.clear();
+        self.output.clear();
+        self.line_count = 0;
         self.indent_level = 0;
     }
 
@@ -82,7 +158,7 @@ impl DslWriter {
 
 impl Display for DslWriter {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.as_output())
+        f.write_str(&self.output)
     }
 }
 
@@ -103,6 +179,8 @@ pub fn format_element_assignment(
             r#"{} = {} "{}" "{}" "{}""#,
             identifier, element_type, name, description, tech
         )
+    } else if description.is_empty() {
+        format!(r#"{} = {} "{}""#, identifier, element_type, name)
     } else {
         format!(
             r#"{} = {} "{}" "{}""#,
@@ -133,6 +211,138 @@ pub fn try_external_output(external_output: &Option<String>) -> Option<String> {
     external_output.as_ref().filter(|s| !s.is_empty()).cloned()
 }
 
+/// Re-indents a raw DSL fragment by brace depth, e.g. hand-written styles
+/// passed to `set_styles_output`. Fails if the braces do not balance.
+pub fn format_dsl(dsl: &str) -> Result<String, DslError> {
+    let mut depth = 0usize;
+    for (index, line) in dsl.lines().enumerate() {
+        for c in line.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth = depth.checked_sub(1).ok_or_else(|| {
+                        DslError::SerializationError(format!(
+                            "unmatched closing brace on line {}",
+                            index + 1
+                        ))
+                    })?
+                }
+                _ => {}
+            }
+        }
+    }
+    if depth > 0 {
+        return Err(DslError::SerializationError(format!(
+            "{} unclosed brace(s)",
+            depth
+        )));
+    }
+    Ok(DslWriter::indent_block(dsl)
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// Strips indentation and blank lines from a raw DSL fragment, keeping one
+/// statement per line like [`DslWriter::set_compact`].
+pub fn minify_dsl(dsl: &str) -> String {
+    dsl.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Checks the statement syntax of the DSL subset c4rs emits: quoted strings
+/// are terminated, braces balance, every `=` assigns to a valid identifier,
+/// and every `->` has an operand on both sides. Comment lines are skipped.
+pub fn validate_dsl_syntax(dsl: &str) -> Result<(), DslError> {
+    let error = |line: usize, message: &str| {
+        DslError::SerializationError(format!("line {}: {}", line, message))
+    };
+    let mut depth = 0usize;
+    for (index, line) in dsl.lines().enumerate() {
+        let number = index + 1;
+        let trimmed = line.trim();
+        if trimmed.starts_with('#') || trimmed.starts_with("//") {
+            continue;
+        }
+        let tokens = tokenize_line(trimmed).ok_or_else(|| error(number, "unterminated string"))?;
+        for (position, token) in tokens.iter().enumerate() {
+            match *token {
+                "{" => depth += 1,
+                "}" => {
+                    depth = depth
+                        .checked_sub(1)
+                        .ok_or_else(|| error(number, "unmatched closing brace"))?
+                }
+                "=" => {
+                    let valid = position == 1
+                        && position + 1 < tokens.len()
+                        && is_valid_identifier(tokens[0]);
+                    if !valid {
+                        return Err(error(number, "assignment needs an identifier and a value"));
+                    }
+                }
+                "->" => {
+                    let is_operand = |token: Option<&&str>| {
+                        token.is_some_and(|t| !t.starts_with('"') && *t != "{")
+                    };
+                    let valid = position > 0
+                        && is_operand(tokens.get(position - 1))
+                        && is_operand(tokens.get(position + 1));
+                    if !valid {
+                        return Err(error(number, "relationship needs a source and a target"));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    if depth > 0 {
+        return Err(DslError::SerializationError(format!(
+            "{} unclosed brace(s)",
+            depth
+        )));
+    }
+    Ok(())
+}
+
+/// Splits a line on whitespace, keeping each quoted string (quotes
+/// included) as one token. Returns `None` if a string is not terminated.
+fn tokenize_line(line: &str) -> Option<Vec<&str>> {
+    let mut tokens = Vec::new();
+    let mut rest = line.trim_start();
+    while !rest.is_empty() {
+        let end = if let Some(quoted) = rest.strip_prefix('"') {
+            let mut escaped = false;
+            quoted.char_indices().find_map(|(i, c)| {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == '"' {
+                    return Some(i + 2);
+                }
+                None
+            })?
+        } else {
+            rest.find(char::is_whitespace).unwrap_or(rest.len())
+        };
+        tokens.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    Some(tokens)
+}
+
+fn is_valid_identifier(identifier: &str) -> bool {
+    !identifier.is_empty()
+        && identifier
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
 pub fn format_parent_reference(child: &str, parent: &str) -> String {
     let child = format_identifier(child);
     let parent = format_identifier(parent);
@@ -148,6 +358,10 @@ mod tests {
         assert_eq!(escape_dsl_string("hello"), "hello");
         assert_eq!(escape_dsl_string("hello\"world"), "hello\\\"world");
         assert_eq!(escape_dsl_string("hello\\world"), "hello\\\\world");
+        assert_eq!(escape_dsl_string("hello\nworld"), "hello\\nworld");
+        assert_eq!(escape_dsl_string("hello\r\nworld"), "hello\\r\\nworld");
+        assert_eq!(escape_dsl_string("v${VERSION}"), "v\\${VERSION}");
+        assert_eq!(escape_dsl_string("$5 {x}"), "$5 {x}");
     }
 
     #[test]
@@ -166,6 +380,9 @@ mod tests {
         let result =
             format_element_assignment("api", "softwareSystem", "API", "Backend API", Some("REST"));
         assert_eq!(result, r#"api = softwareSystem "API" "Backend API" "REST""#);
+
+        let result = format_element_assignment("api", "softwareSystem", "API", "", None);
+        assert_eq!(result, r#"api = softwareSystem "API""#);
     }
 
     #[test]
@@ -176,4 +393,118 @@ mod tests {
         let result = format_relationship("api", "db", "Reads from", Some("JDBC"));
         assert_eq!(result, r#"api -> db "Reads from" "JDBC""#);
     }
+
+    #[test]
+    fn test_writer_output_matches_joined_lines() {
+        let mut writer = DslWriter::with_capacity(1);
+        writer.add_empty_line();
+        writer.write_block("workspace", |w| {
+            w.add_line("a = softwareSystem \"A\" {\n    tags \"External\"");
+            w.add_empty_line();
+            w.write_block("views", |w| w.add_line("include *"));
+        });
+        let expected = [
+            "",
+            "workspace {",
+            "    a = softwareSystem \"A\" {\n    tags \"External\"",
+            "",
+            "    views {",
+            "        include *",
+            "    }",
+            "}",
+        ]
+        .join("\n");
+        assert_eq!(writer.as_output(), expected);
+        assert_eq!(writer.to_string(), expected);
+        assert_eq!(writer.line_count(), expected.lines().count());
+
+        writer.clear();
+        assert_eq!(writer.line_count(), 0);
+        writer.add_line("x");
+        assert_eq!(writer.into_output(), "x");
+    }
+
+    #[test]
+    fn test_compact_writer() {
+        let mut writer = DslWriter::new();
+        writer.set_compact(true);
+        writer.write_block("model", |w| {
+            w.add_line("u = person \"User\"");
+            w.add_empty_line();
+            w.add_line("    nested {\n        tags \"External\"\n    }");
+        });
+        assert_eq!(
+            writer.as_output(),
+            "model {\nu = person \"User\"\nnested {\ntags \"External\"\n}\n}"
+        );
+    }
+
+    #[test]
+    fn test_format_dsl_round_trip() {
+        let messy = "styles {\n  element \"Person\" {\n\t\tshape Person   \n}\n\n        relationship \"Relationship\" {\nthickness 2\n      }\n   }";
+        let expected = [
+            "styles {",
+            "    element \"Person\" {",
+            "        shape Person",
+            "    }",
+            "",
+            "    relationship \"Relationship\" {",
+            "        thickness 2",
+            "    }",
+            "}",
+        ]
+        .join("\n");
+
+        let formatted = format_dsl(messy).unwrap();
+        assert_eq!(formatted, expected);
+        assert_eq!(format_dsl(&formatted).unwrap(), expected);
+        assert_eq!(
+            format_dsl(&minify_dsl(messy)).unwrap(),
+            expected.replace("\n\n", "\n")
+        );
+    }
+
+    #[test]
+    fn test_format_dsl_unbalanced_braces() {
+        assert!(matches!(
+            format_dsl("styles {\n}\n}"),
+            Err(DslError::SerializationError(_))
+        ));
+        assert!(matches!(
+            format_dsl("styles {"),
+            Err(DslError::SerializationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_dsl_syntax() {
+        let valid = "workspace {\n    model {\n        # \"note\n        u = person \"User\" \"Says \\\"a -> b\\\" {\"\n        s = softwareSystem \"Shop\"\n        u -> s \"Uses\"\n    }\n}";
+        assert!(validate_dsl_syntax(valid).is_ok());
+
+        for corrupted in [
+            "u = person \"User",
+            "= person \"User\"",
+            "u s = person \"User\"",
+            "u -> \"Uses\"",
+            "-> s \"Uses\"",
+            "model {\n    s = softwareSystem \"Shop\"",
+            "}",
+        ] {
+            assert!(
+                matches!(
+                    validate_dsl_syntax(corrupted),
+                    Err(DslError::SerializationError(_))
+                ),
+                "{corrupted}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_minify_dsl() {
+        assert_eq!(
+            minify_dsl("styles {\n\n    element \"Person\" {\n        shape Person\n    }\n}\n"),
+            "styles {\nelement \"Person\" {\nshape Person\n}\n}"
+        );
+    }
 }
@@ -2,7 +2,7 @@
 //!
 //! Run with: cargo run --example self_diagram
 
-use c4rs::c4::ContainerType;
+use c4rs::c4::{ComponentVisibility, ContainerType};
 use c4rs::{
     CodeElement, CodeType, Component, Container, DslSerializer, ElementStyle, Person,
     RelationshipStyle, SoftwareSystem, ViewConfiguration, ViewType,
@@ -269,6 +269,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         .name("Public API".into())
         .description("lib.rs - public exports (DslSerializer, etc.)".into())
         .technology("Rust".into())
+        .visibility(ComponentVisibility::Public)
         .add_code_element(&dsl_mod_rs)
         .build()?;
 
@@ -276,6 +277,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         .name("Implementation".into())
         .description("Internal serialization implementation details".into())
         .technology("Rust".into())
+        .visibility(ComponentVisibility::Internal)
         .add_code_element(&serializer_code)
         .add_code_element(&workspace_code)
         .add_code_element(&identifier_code)
@@ -4,12 +4,23 @@
 //! for architecture documentation and diagram generation.
 //!
 //! See [`c4rs_core`] and [`c4rs_structurizr_dsl`] crates for details.
+//! [`prelude`] re-exports the types needed for typical usage.
+
+pub mod prelude;
 
 pub use c4rs_core::c4;
-pub use c4rs_core::{CodeElement, Component, Container, Person, Relationship, SoftwareSystem};
-pub use c4rs_core::{CodeType, ContainerType, ElementType, InteractionStyle, Location};
+pub use c4rs_core::{
+    CodeElement, Component, Container, Person, Relationship, RelationshipTechnology, SoftwareSystem,
+};
+pub use c4rs_core::{
+    CodeType, ComponentVisibility, ContainerType, ElementType, InteractionStyle, Location,
+};
 
-pub use c4rs_structurizr_dsl::DslError;
-pub use c4rs_structurizr_dsl::DslSerializer;
-pub use c4rs_structurizr_dsl::{ElementStyle, RelationshipStyle, StylesSerializer};
-pub use c4rs_structurizr_dsl::{ViewConfiguration, ViewType, ViewsSerializer};
+pub use c4rs_structurizr_dsl::{
+    BorderStyle, ElementStyle, RelationshipStyle, Router, Shape, StylesSerializer,
+};
+pub use c4rs_structurizr_dsl::{DslError, DslWarning};
+pub use c4rs_structurizr_dsl::{
+    DslSerializer, IdentifierHeader, IdentifierMode, STABLE_FORMAT_VERSION,
+};
+pub use c4rs_structurizr_dsl::{StylesPosition, ViewConfiguration, ViewType, ViewsSerializer};
@@ -0,0 +1,48 @@
+//! Common types for building and serializing a model with a single import.
+//!
+//! ```
+//! use c4rs::prelude::*;
+//!
+//! let user = Person::builder()
+//!     .name("User".into())
+//!     .description("A user".into())
+//!     .build()
+//!     .unwrap();
+//! let shop = SoftwareSystem::builder()
+//!     .name("Shop".into())
+//!     .description("Online shop".into())
+//!     .build()
+//!     .unwrap();
+//!
+//! let dsl = DslSerializer::new()
+//!     .with_name("Shop")
+//!     .add_person(&user)
+//!     .add_software_system(&shop)
+//!     .add_relationship(&user, &shop, "Uses", None)
+//!     .add_view(
+//!         ViewConfiguration::builder()
+//!             .view_type(ViewType::SystemContext)
+//!             .element_identifier(shop.id().as_str().to_string())
+//!             .title("Context".into())
+//!             .build(),
+//!     )
+//!     .add_element_style(
+//!         ElementStyle::builder()
+//!             .identifier("Person".into())
+//!             .shape_enum(Shape::Person)
+//!             .build(),
+//!     )
+//!     .serialize()
+//!     .unwrap();
+//!
+//! assert!(dsl.contains(r#"u -> s "Uses""#));
+//! ```
+
+pub use c4rs_core::c4::{
+    CodeElement, CodeType, Component, ComponentVisibility, Container, ContainerType, Element,
+    ElementType, InteractionStyle, Location, Person, Relationship, SoftwareSystem,
+};
+pub use c4rs_structurizr_dsl::{
+    BorderStyle, DslError, DslSerializer, ElementStyle, RelationshipStyle, Router, Shape,
+    ViewConfiguration, ViewType,
+};
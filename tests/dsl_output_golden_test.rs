@@ -3,10 +3,12 @@
 
 use c4rs::c4::ContainerType;
 use c4rs::{
-    Component, Container, DslSerializer, ElementStyle, Person, RelationshipStyle, SoftwareSystem,
-    ViewConfiguration, ViewType,
+    CodeElement, CodeType, Component, Container, DslSerializer, ElementStyle, Person,
+    RelationshipStyle, SoftwareSystem, ViewConfiguration, ViewType,
 };
 
+mod support;
+
 #[test]
 fn test_golden_self_diagram_output() {
     let library_consumer = Person::builder()
@@ -15,10 +17,28 @@ fn test_golden_self_diagram_output() {
         .build()
         .unwrap();
 
+    // Code elements are only written as component properties; they must not
+    // take identifiers from the containers and systems declared after them.
+    let container_rs = CodeElement::builder()
+        .name("container.rs".into())
+        .description("Container type".into())
+        .code_type(CodeType::Module)
+        .build()
+        .unwrap();
+
+    let system_rs = CodeElement::builder()
+        .name("system.rs".into())
+        .description("SoftwareSystem type".into())
+        .code_type(CodeType::Module)
+        .build()
+        .unwrap();
+
     let element_trait = Component::builder()
         .name("Element Trait".into())
         .description("Common trait implemented by all C4 element types".into())
         .technology("Rust Trait".into())
+        .add_code_element(&container_rs)
+        .add_code_element(&system_rs)
         .build()
         .unwrap();
 
@@ -176,7 +196,7 @@ fn test_golden_self_diagram_output() {
         .build()
         .unwrap();
 
-    let dsl = DslSerializer::new()
+    let serializer = DslSerializer::new()
         .with_name("c4rs Architecture")
         .with_description("C4 model of the c4rs Rust library itself")
         .add_person(&library_consumer)
@@ -287,89 +307,13 @@ fn test_golden_self_diagram_output() {
                 .color("#707070".into())
                 .dashed(false)
                 .build(),
-        )
-        .serialize()
-        .unwrap();
+        );
 
-    let expected = r#"workspace "c4rs Architecture" "C4 model of the c4rs Rust library itself" {
-    !identifiers hierarchical
+    // 1 person, 2 systems, 3 containers, 8 + 7 components, 2 code elements
+    assert_eq!(serializer.count_elements(), 23);
+    assert_eq!(serializer.count_relationships(), 5);
 
-    model {
-        lc = person "Library Consumer" "A Rust developer using c4rs to model their architecture"
-        c = softwareSystem "c4rs" "Rust library for defining C4 architecture models with pluggable serialization" {
-            c1 = container "c4rs" "Umbrella crate that re-exports core types and DSL serializer" {}
-            c2 = container "c4rs-core" "Core C4 model types, traits, validation, and builders" {
-                et = component "Element Trait" "Common trait implemented by all C4 element types" "Rust Trait"
-                ct = component "Context Types" "Person and SoftwareSystem types for the context level" "Rust"
-                ct1 = component "Container Type" "Container type with nested component ownership" "Rust"
-                ct2 = component "Component Type" "Component type with nested code element ownership" "Rust"
-                cet = component "Code Element Type" "CodeElement type for classes, functions, structs, etc." "Rust"
-                r = component "Relationship" "Generic Relationship<S, T> between any two Element types" "Rust Generics"
-                v = component "Validation" "Input validation for names, descriptions, and field lengths" "Rust"
-                b = component "Builders" "Fallible builder pattern via bon with validation on build()" "bon"
-            }
-            c3 = container "c4rs-structurizr-dsl" "Structurizr DSL serialization module" {
-                d = component "DslSerializer" "Consuming-builder facade for assembling a complete workspace" "Rust"
-                w = component "WorkspaceSerializer" "Core serialization engine that walks the model and emits DSL" "Rust"
-                i = component "IdentifierGenerator" "Auto-generates unique DSL identifiers from element names" "Rust"
-                d1 = component "DslWriter" "Indentation-aware string builder for DSL output" "Rust"
-                v1 = component "ViewsSerializer" "Renders view blocks (systemContext, container, component, etc.)" "Askama"
-                s = component "StylesSerializer" "Renders element and relationship style blocks" "Askama"
-                at = component "Askama Templates" "Inline Askama templates for DSL fragment rendering" "Askama"
-            }
-        }
-        s1 = softwareSystem "Structurizr" "Renders architecture diagrams from Structurizr DSL files"
-        lc -> c "Uses" "Cargo dependency"
-        c.c1 -> c.c2 "Re-exports types from"
-        c.c1 -> c.c3 "Re-exports serializer from"
-        c.c3 -> c.c2 "Depends on" "Cargo path dep"
-        c -> s1 "Produces DSL for" "Structurizr DSL"
-    }
-
-    views {
-        systemLandscape "System_Landscape" {
-            include *
-        }
-        systemContext c "c4rs_System_Context" {
-            include *
-        }
-        container c "c4rs_Containers" {
-            include *
-        }
-        component c.c1 "c4rs-core_Components" {
-            include *
-        }
-        component c.c2 "c4rs-structurizr-dsl_Components" {
-            include *
-        }
-
-        styles {
-            element "Person" {
-                background #08427B
-                color #ffffff
-                shape person
-            }
-            element "Software System" {
-                background #1168BD
-                color #ffffff
-            }
-            element "Container" {
-                background #438DD5
-                color #ffffff
-            }
-            element "Component" {
-                background #85BBF0
-                color #000000
-            }
-            relationship "Relationship" {
-                color #707070
-                dashed false
-            }
-        }
-    }
-}"#;
-
-    assert_eq!(dsl, expected);
+    support::assert_serializes_to(serializer, "self_diagram.dsl");
 }
 
 #[test]
@@ -580,3 +524,93 @@ fn test_golden_hierarchical_relationship_identifiers_resolved() {
     // The serializer resolves container IDs to their hierarchical paths
     assert!(dsl.contains(r#"s.a -> s.d "Reads from" "SQL""#));
 }
+
+/// Pins the output of `serialize_stable` for `STABLE_FORMAT_VERSION` 1.
+/// This expectation must not change within a major release.
+#[test]
+fn test_golden_serialize_stable_v1() {
+    let user = Person::builder()
+        .name("User".into())
+        .description("A user of the system".into())
+        .build()
+        .unwrap();
+
+    let handler = Component::builder()
+        .name("Handler".into())
+        .description("Handles requests".into())
+        .technology("Rust".into())
+        .build()
+        .unwrap();
+
+    let api = Container::builder()
+        .name("API".into())
+        .description("Backend API".into())
+        .container_type(ContainerType::Api)
+        .add_component(&handler)
+        .build()
+        .unwrap();
+
+    let system = SoftwareSystem::builder()
+        .name("Shop".into())
+        .description("Online shop".into())
+        .add_container(&api)
+        .build()
+        .unwrap();
+
+    let dsl = DslSerializer::new()
+        .with_name("Stable")
+        .with_description("Stable formatting")
+        .with_compact(true)
+        .add_person(&user)
+        .add_software_system(&system)
+        .add_relationship(&user, &api, "Uses", Some("HTTPS"))
+        .add_view(
+            ViewConfiguration::builder()
+                .view_type(ViewType::Container)
+                .element_identifier("s".into())
+                .title("Containers".into())
+                .include_elements(vec!["*".into()])
+                .build(),
+        )
+        .add_element_style(
+            ElementStyle::builder()
+                .identifier("Person".into())
+                .shape("person".into())
+                .build(),
+        )
+        .add_relationship_style(RelationshipStyle::builder().color("#707070".into()).build())
+        .serialize_stable()
+        .unwrap();
+
+    assert_eq!(c4rs::STABLE_FORMAT_VERSION, 1);
+    let expected = r#"workspace "Stable" "Stable formatting" {
+    !identifiers hierarchical
+
+    model {
+        u = person "User" "A user of the system"
+        s = softwareSystem "Shop" "Online shop" {
+            a = container "API" "Backend API" {
+                h = component "Handler" "Handles requests" "Rust"
+            }
+        }
+        u -> s.a "Uses" "HTTPS"
+    }
+
+    views {
+        container s "Containers" {
+            include *
+        }
+
+        styles {
+            element "Person" {
+                shape person
+            }
+            relationship "Relationship" {
+                color #707070
+            }
+        }
+    }
+}"#;
+
+    assert_eq!(dsl, expected);
+}
@@ -0,0 +1,36 @@
+//! Shared helpers for integration tests.
+
+use c4rs::DslSerializer;
+use std::path::PathBuf;
+
+/// Set to regenerate golden files instead of comparing against them.
+const UPDATE_ENV: &str = "C4RS_UPDATE_GOLDEN";
+
+/// Serializes `serializer` and compares the output with `tests/golden/<name>`.
+/// With `C4RS_UPDATE_GOLDEN=1` set, the file is (re)written instead, so
+/// intended output changes can be accepted with a single test run.
+pub fn assert_serializes_to(serializer: DslSerializer, name: &str) {
+    let dsl = serializer.serialize().expect("serialization failed");
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "golden", name]
+        .iter()
+        .collect();
+
+    if std::env::var_os(UPDATE_ENV).is_some() {
+        std::fs::write(&path, &dsl)
+            .unwrap_or_else(|e| panic!("cannot write {}: {e}", path.display()));
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "cannot read {}: {e}; run with {UPDATE_ENV}=1 to create it",
+            path.display()
+        )
+    });
+    assert_eq!(
+        dsl,
+        expected,
+        "output differs from {}; run with {UPDATE_ENV}=1 to update it",
+        path.display()
+    );
+}